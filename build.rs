@@ -51,4 +51,9 @@ fn main() {
         println!("cargo:rustc-link-lib={}", lib);
     }
 
+    // Link libtesseract only when the feature asks for it.
+    if env::var("CARGO_FEATURE_TESSERACT").is_ok() {
+        status.write_fmt(format_args!("tesseract: enabled\n")).unwrap();
+        pkg_config::Config::new().atleast_version("3.04").probe("tesseract").expect("Could not find tesseract");
+    }
 }
\ No newline at end of file
@@ -0,0 +1,236 @@
+//! Degradations that make synthetic tables look like published results.
+//!
+//! Every transform updates the ground-truth cell rectangles along with the
+//! image, so detection accuracy can still be measured afterwards.
+
+use image::{self, DynamicImage, GenericImage, ImageBuffer, ImageResult, Rgba, RgbaImage};
+use image::jpeg::JPEGEncoder;
+
+use geometry;
+use perspective::{self, Homography};
+use synth::{Rng, Synthetic, TruthCell};
+
+
+/// JPEG qualities commonly seen in published results.
+const JPEG_QUALITIES: &[u8] = &[30, 50, 70, 85, 95];
+
+
+/// A single kind of damage to a synthetic table.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Degradation {
+    /// Recompress as a JPEG at the given quality, from 1 to 100.
+    Jpeg { quality: u8 },
+    /// Resize by the given factor. Below 1.0 downscales, above upscales.
+    Rescale { factor: f32 },
+    /// Gaussian blur with the given standard deviation, in pixels.
+    Blur { sigma: f32 },
+    /// Rotate counterclockwise about the center, keeping the image size.
+    Rotate { degrees: f32 },
+    /// Pull the top corners inward by the given fraction of the width,
+    /// as in a photo taken from below the sheet.
+    Perspective { inset: f32 },
+    /// Add Gaussian noise with the given standard deviation, in levels.
+    Noise { sigma: f32 },
+    /// Smear color horizontally over the given radius, as chroma
+    /// subsampling does, while keeping brightness sharp.
+    ChromaBleed { radius: u32 },
+}
+
+impl Degradation {
+    /// Picks a random degradation of plausible strength.
+    pub fn random(rng: &mut Rng) -> Degradation {
+        match rng.range(0, 6) {
+            0 => Degradation::Jpeg { quality: *rng.choose(JPEG_QUALITIES) },
+            1 => Degradation::Rescale { factor: 0.6 + rng.unit() * 1.4 },
+            2 => Degradation::Blur { sigma: 0.3 + rng.unit() * 0.9 },
+            3 => Degradation::Rotate { degrees: (rng.unit() - 0.5) * 3.0 },
+            4 => Degradation::Noise { sigma: 2.0 + rng.unit() * 10.0 },
+            5 => Degradation::Perspective { inset: 0.01 + rng.unit() * 0.04 },
+            _ => Degradation::ChromaBleed { radius: rng.range(1, 3) },
+        }
+    }
+
+    /// Applies the degradation to the image and its ground truth.
+    pub fn apply(&self, table: &mut Synthetic, rng: &mut Rng) -> ImageResult<()> {
+        match *self {
+            Degradation::Jpeg { quality } => {
+                table.image = recompress(&table.image, quality)?;
+            }
+            Degradation::Rescale { factor } => {
+                let (width, height) = table.image.dimensions();
+                let new_width = u32::max((width as f32 * factor).round() as u32, 1);
+                let new_height = u32::max((height as f32 * factor).round() as u32, 1);
+                table.image = table.image.resize_exact(new_width, new_height, image::Triangle);
+
+                let sx = new_width as f32 / width as f32;
+                let sy = new_height as f32 / height as f32;
+                for cell in &mut table.cells {
+                    scale_cell(cell, sx, sy);
+                }
+            }
+            Degradation::Blur { sigma } => {
+                table.image = table.image.blur(sigma);
+            }
+            Degradation::Rotate { degrees } => {
+                let radians = degrees.to_radians();
+                let background = geometry::background_color(&table.image);
+                table.image = geometry::rotate(&table.image, radians, background);
+
+                let (width, height) = table.image.dimensions();
+                let (cx, cy) = ((width as f32 - 1.0) / 2.0, (height as f32 - 1.0) / 2.0);
+                for cell in &mut table.cells {
+                    transform_cell(cell, |x, y| geometry::rotate_point(x, y, cx, cy, radians), width, height);
+                }
+            }
+            Degradation::Perspective { inset } => {
+                let (width, height) = table.image.dimensions();
+                let (right, bottom) = ((width - 1) as f32, (height - 1) as f32);
+                let pull = inset * right;
+
+                let rectangle = [(0.0, 0.0), (right, 0.0), (right, bottom), (0.0, bottom)];
+                let trapezoid = [(pull, 0.0), (right - pull, 0.0), (right, bottom), (0.0, bottom)];
+                let forward = Homography::from_points(&rectangle, &trapezoid).unwrap();
+                let backward = Homography::from_points(&trapezoid, &rectangle).unwrap();
+
+                table.image = perspective::warp(&table.image, &backward, width, height);
+                for cell in &mut table.cells {
+                    transform_cell(cell, |x, y| forward.apply(x, y), width, height);
+                }
+            }
+            Degradation::Noise { sigma } => {
+                table.image = add_noise(&table.image, sigma, rng);
+            }
+            Degradation::ChromaBleed { radius } => {
+                table.image = bleed_chroma(&table.image, radius);
+            }
+        }
+        Ok(())
+    }
+}
+
+
+/// Applies each degradation in turn.
+pub fn apply_all(table: &mut Synthetic, degradations: &[Degradation], rng: &mut Rng) -> ImageResult<()> {
+    for degradation in degradations {
+        degradation.apply(table, rng)?;
+    }
+    Ok(())
+}
+
+
+/// Picks `count` random degradations and applies them.
+/// Returns what was applied, in order.
+pub fn degrade(table: &mut Synthetic, count: u32, rng: &mut Rng) -> ImageResult<Vec<Degradation>> {
+    let degradations: Vec<Degradation> = (0 .. count).map(|_| Degradation::random(rng)).collect();
+    apply_all(table, &degradations, rng)?;
+    Ok(degradations)
+}
+
+
+/// Round-trips the image through the JPEG encoder.
+fn recompress(img: &DynamicImage, quality: u8) -> ImageResult<DynamicImage> {
+    let rgb = img.to_rgb();
+    let (width, height) = rgb.dimensions();
+
+    let mut buffer = Vec::new();
+    JPEGEncoder::new_with_quality(&mut buffer, quality)
+        .encode(&rgb, width, height, image::RGB(8))?;
+
+    image::load_from_memory_with_format(&buffer, image::JPEG)
+}
+
+
+fn scale_cell(cell: &mut TruthCell, sx: f32, sy: f32) {
+    let left = (cell.x as f32 * sx).round() as u32;
+    let top = (cell.y as f32 * sy).round() as u32;
+    let right = ((cell.x + cell.width) as f32 * sx).round() as u32;
+    let bottom = ((cell.y + cell.height) as f32 * sy).round() as u32;
+
+    cell.x = left;
+    cell.y = top;
+    cell.width = right.saturating_sub(left);
+    cell.height = bottom.saturating_sub(top);
+}
+
+
+/// Replaces the cell with the bounding box of its transformed corners,
+/// clipped to the image.
+fn transform_cell<F: Fn(f32, f32) -> (f32, f32)>(cell: &mut TruthCell, f: F, width: u32, height: u32) {
+    let corners = [
+        (cell.x as f32, cell.y as f32),
+        ((cell.x + cell.width) as f32, cell.y as f32),
+        (cell.x as f32, (cell.y + cell.height) as f32),
+        ((cell.x + cell.width) as f32, (cell.y + cell.height) as f32),
+    ];
+
+    let moved: Vec<(f32, f32)> = corners.iter().map(|&(x, y)| f(x, y)).collect();
+
+    let clamp = |v: f32, max: u32| v.clamp(0.0, max as f32).round() as u32;
+    let left = clamp(moved.iter().map(|p| p.0).fold(f32::INFINITY, f32::min), width);
+    let right = clamp(moved.iter().map(|p| p.0).fold(f32::NEG_INFINITY, f32::max), width);
+    let top = clamp(moved.iter().map(|p| p.1).fold(f32::INFINITY, f32::min), height);
+    let bottom = clamp(moved.iter().map(|p| p.1).fold(f32::NEG_INFINITY, f32::max), height);
+
+    cell.x = left;
+    cell.y = top;
+    cell.width = right - left;
+    cell.height = bottom - top;
+}
+
+
+/// Draws a standard normal sample using the Box-Muller transform.
+fn gaussian(rng: &mut Rng) -> f32 {
+    let u1 = rng.unit().max(f32::MIN_POSITIVE);
+    let u2 = rng.unit();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * ::std::f32::consts::PI * u2).cos()
+}
+
+
+fn add_noise(img: &DynamicImage, sigma: f32, rng: &mut Rng) -> DynamicImage {
+    let mut out = img.to_rgba();
+    for px in out.pixels_mut() {
+        // The same offset on every channel, as sensor noise mostly is.
+        let offset = gaussian(rng) * sigma;
+        for c in 0 .. 3 {
+            px.data[c] = (px.data[c] as f32 + offset).clamp(0.0, 255.0).round() as u8;
+        }
+    }
+    DynamicImage::ImageRgba8(out)
+}
+
+
+/// Averages the chroma of each pixel with its horizontal neighbors,
+/// leaving the luma of each pixel as it was.
+fn bleed_chroma(img: &DynamicImage, radius: u32) -> DynamicImage {
+    let src = img.to_rgba();
+    let (width, height) = src.dimensions();
+
+    let to_ycc = |p: &Rgba<u8>| {
+        let (r, g, b) = (p.data[0] as f32, p.data[1] as f32, p.data[2] as f32);
+        let y = 0.299 * r + 0.587 * g + 0.114 * b;
+        (y, b - y, r - y)
+    };
+
+    let out: RgbaImage = ImageBuffer::from_fn(width, height, |x, y| {
+        let (luma, _, _) = to_ycc(src.get_pixel(x, y));
+
+        let lo = x.saturating_sub(radius);
+        let hi = u32::min(x + radius, width - 1);
+        let (mut cb, mut cr) = (0.0, 0.0);
+        for k in lo ..= hi {
+            let (_, b, r) = to_ycc(src.get_pixel(k, y));
+            cb += b;
+            cr += r;
+        }
+        let n = (hi - lo + 1) as f32;
+        let (cb, cr) = (cb / n, cr / n);
+
+        let r = luma + cr;
+        let b = luma + cb;
+        let g = (luma - 0.299 * r - 0.114 * b) / 0.587;
+        let clamp = |v: f32| v.clamp(0.0, 255.0).round() as u8;
+        Rgba([clamp(r), clamp(g), clamp(b), src.get_pixel(x, y).data[3]])
+    });
+
+    DynamicImage::ImageRgba8(out)
+}
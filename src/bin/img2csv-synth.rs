@@ -0,0 +1,118 @@
+//! Renders synthetic spreadsheet screenshots with their ground truth.
+//!
+//! Tables are numbered from zero with four digits. For each table NNNN,
+//! writes `NNNN.png`, the expected `NNNN.csv`, and the cell rectangles as
+//! `NNNN.cells.csv`. With `--degrade K`, each table also suffers K random
+//! degradations, listed in `NNNN.degradations.txt`. `--font bitmap` or
+//! `--font sans` letters every table in that typeface.
+
+extern crate img2csv;
+extern crate image;
+
+use img2csv::augment;
+use img2csv::synth::{self, Font, Rng, Style};
+
+use std::env;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process;
+
+/// The size of `--font sans`, in pixels to the em.
+const SANS_SIZE: f32 = 14.0;
+
+struct Options {
+    out_dir: PathBuf,
+    count: u32,
+    seed: u64,
+    plain: bool,
+    degrade: u32,
+    font: Option<Font>,
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut args = env::args().skip(1);
+    let mut out_dir = None;
+    let mut count = 10;
+    let mut seed = 0;
+    let mut plain = false;
+    let mut degrade = 0;
+    let mut font = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--count" | "--seed" | "--degrade" => {
+                let value = args.next().ok_or(format!("Missing value after {}.", arg))?;
+                let invalid = |_| format!("Invalid number: {}", value);
+                match arg.as_str() {
+                    "--count" => count = value.parse::<u32>().map_err(invalid)?,
+                    "--seed" => seed = value.parse::<u64>().map_err(invalid)?,
+                    _ => degrade = value.parse::<u32>().map_err(invalid)?,
+                }
+            }
+            "--font" => {
+                let value = args.next().ok_or(format!("Missing value after {}.", arg))?;
+                font = Some(match value.as_str() {
+                    "bitmap" => Font::Bitmap,
+                    "sans" => Font::Sans(SANS_SIZE),
+                    _ => return Err(format!("Unknown font: {}", value)),
+                });
+            }
+            "--plain" => plain = true,
+            _ if arg.starts_with('-') => return Err(format!("Unknown option: {}", arg)),
+            _ => {
+                if out_dir.is_some() {
+                    return Err("Too many directory arguments.".to_string());
+                }
+                out_dir = Some(PathBuf::from(arg));
+            }
+        }
+    }
+
+    let out_dir = out_dir.ok_or("Usage: img2csv-synth OUTDIR [--count N] [--seed S] [--plain] \
+                                 [--degrade K] [--font bitmap|sans]")?;
+    Ok(Options { out_dir, count, seed, plain, degrade, font })
+}
+
+fn run(options: Options) -> Result<(), Box<Error>> {
+    fs::create_dir_all(&options.out_dir)?;
+
+    let mut rng = Rng::new(options.seed);
+    for i in 0 .. options.count {
+        // Black gridlines on white, or a random look.
+        let mut style = if options.plain { Style::default() } else { Style::random(&mut rng) };
+        if let Some(font) = options.font {
+            style.font = font;
+        }
+        let mut table = synth::generate(&mut rng, &style);
+        let stem = format!("{:04}", i);
+
+        if options.degrade > 0 {
+            let applied = augment::degrade(&mut table, options.degrade, &mut rng)?;
+            let mut log = File::create(options.out_dir.join(format!("{}.degradations.txt", stem)))?;
+            for degradation in applied {
+                writeln!(log, "{:?}", degradation)?;
+            }
+        }
+
+        let mut png = File::create(options.out_dir.join(format!("{}.png", stem)))?;
+        table.image.save(&mut png, image::PNG)?;
+        table.write_csv(&mut File::create(options.out_dir.join(format!("{}.csv", stem)))?)?;
+        table.write_geometry(&mut File::create(options.out_dir.join(format!("{}.cells.csv", stem)))?)?;
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let options = parse_args().unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {}", err);
+        process::exit(1);
+    });
+
+    if let Err(e) = run(options) {
+        eprintln!("Application error: {}", e);
+        process::exit(1);
+    }
+}
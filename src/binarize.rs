@@ -0,0 +1,308 @@
+//! Separation of ink from background.
+//!
+//! Features are pixels that are darker than a threshold. The threshold
+//! can be fixed, chosen once for the whole image, or chosen per pixel
+//! from its neighborhood, which copes with faint gridlines, colored
+//! rules and shaded rows. Images with light ink on a dark background,
+//! such as dark mode screenshots, are inverted first.
+
+use image::{DynamicImage, GenericImage, GrayImage, ImageBuffer, Luma};
+
+/// The dynamic range of the standard deviation, used by Sauvola's method.
+const SAUVOLA_RANGE: f32 = 128.0;
+
+/// How to choose the darkness threshold for features.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Binarization {
+    /// Pixels at or below this luma are features.
+    Fixed(u8),
+    /// One threshold for the whole image, chosen by Otsu's method.
+    Otsu,
+    /// A threshold for each pixel from the mean and deviation of the
+    /// `window` by `window` square around it. Larger `k` keeps less ink.
+    Sauvola { window: u32, k: f32 },
+    /// Pixels that are at least `min_contrast` darker than the background
+    /// `radius` pixels away on both sides, in some direction. Thin rules
+    /// and strokes of any color pass; fills wider than the radius do not.
+    Contrast { radius: u32, min_contrast: u8 },
+}
+
+impl Default for Binarization {
+    fn default() -> Binarization {
+        Binarization::Contrast { radius: 4, min_contrast: 40 }
+    }
+}
+
+impl Binarization {
+    /// Parses `otsu`, `sauvola`, `contrast`, or `fixed:N`.
+    pub fn from_name(name: &str) -> Option<Binarization> {
+        match name {
+            "otsu" => Some(Binarization::Otsu),
+            "sauvola" => Some(Binarization::Sauvola { window: 25, k: 0.2 }),
+            "contrast" => Some(Binarization::Contrast { radius: 4, min_contrast: 40 }),
+            _ if name.starts_with("fixed:") => {
+                name["fixed:".len() ..].parse().ok().map(Binarization::Fixed)
+            }
+            _ => None,
+        }
+    }
+}
+
+
+/// Whether the ink is darker or lighter than the background.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Polarity {
+    /// Decide from the image.
+    #[default]
+    Auto,
+    /// Dark ink on a light background.
+    Normal,
+    /// Light ink on a dark background, as in dark mode.
+    Inverted,
+}
+
+impl Polarity {
+    /// Parses `auto`, `normal`, or `inverted`.
+    pub fn from_name(name: &str) -> Option<Polarity> {
+        match name {
+            "auto" => Some(Polarity::Auto),
+            "normal" => Some(Polarity::Normal),
+            "inverted" => Some(Polarity::Inverted),
+            _ => None,
+        }
+    }
+}
+
+
+/// Returns the image as dark ink on a light background,
+/// and whether it had to be inverted to get there.
+pub fn normalize(img: &DynamicImage, polarity: Polarity) -> (DynamicImage, bool) {
+    let inverted = match polarity {
+        Polarity::Auto => median(&histogram(&img.to_luma())) < 128,
+        Polarity::Normal => false,
+        Polarity::Inverted => true,
+    };
+
+    let mut img = img.clone();
+    if inverted {
+        img.invert();
+    }
+    (img, inverted)
+}
+
+/// Which pixels of an image are ink.
+pub struct Ink {
+    width: u32,
+    mask: Vec<bool>,
+    threshold: f32,
+}
+
+impl Ink {
+    /// Whether the pixel at (x, y) is ink.
+    #[inline]
+    pub fn at(&self, x: u32, y: u32) -> bool {
+        self.mask[(y * self.width + x) as usize]
+    }
+
+    /// The average threshold over the image, for reporting.
+    pub fn mean_threshold(&self) -> f32 {
+        self.threshold
+    }
+}
+
+
+/// Finds the ink in an image.
+pub fn ink(img: &DynamicImage, mode: &Binarization) -> Ink {
+    let (width, height) = img.dimensions();
+
+    // Colored rules can be as bright as the fills next to them, so local
+    // contrast looks at each channel on its own rather than at luma.
+    let channels: Vec<GrayImage> = match *mode {
+        Binarization::Contrast { .. } => {
+            let rgb = img.to_rgb();
+            (0 .. 3).map(|c| ImageBuffer::from_fn(width, height, |x, y| {
+                Luma([rgb.get_pixel(x, y).data[c]])
+            })).collect()
+        }
+        _ => vec![img.to_luma()],
+    };
+
+    let mut mask = vec![false; (width * height) as usize];
+    let mut total = 0.0f64;
+    for channel in &channels {
+        let thresholds = thresholds(channel, mode);
+        for (i, px) in channel.pixels().enumerate() {
+            mask[i] |= px.data[0] <= thresholds[i];
+        }
+        total += thresholds.iter().map(|&t| t as f64).sum::<f64>();
+    }
+
+    let count = (mask.len() * channels.len()) as f64;
+    let threshold = if count > 0.0 { (total / count) as f32 } else { 0.0 };
+    Ink { width, mask, threshold }
+}
+
+
+/// Chooses a threshold for every pixel of a single-channel image.
+fn thresholds(gray: &GrayImage, mode: &Binarization) -> Vec<u8> {
+    let (width, height) = gray.dimensions();
+    let len = (width * height) as usize;
+
+    match *mode {
+        Binarization::Fixed(t) => vec![t; len],
+        Binarization::Otsu => vec![otsu_threshold(&histogram(gray)); len],
+        Binarization::Sauvola { window, k } => sauvola(gray, window, k),
+        Binarization::Contrast { radius, min_contrast } => contrast(gray, radius, min_contrast),
+    }
+}
+
+
+/// Counts the pixels at each luma.
+pub fn histogram(gray: &GrayImage) -> [u32; 256] {
+    let mut histogram = [0u32; 256];
+    for px in gray.pixels() {
+        histogram[px.data[0] as usize] += 1;
+    }
+    histogram
+}
+
+
+/// The median value of the histogram. Most of a table is background,
+/// so this is close to the background's luma.
+fn median(histogram: &[u32; 256]) -> u8 {
+    let total: u64 = histogram.iter().map(|&n| n as u64).sum();
+    let mut seen = 0;
+    for (value, &n) in histogram.iter().enumerate() {
+        seen += n as u64;
+        if seen * 2 >= total {
+            return value as u8;
+        }
+    }
+    255
+}
+
+
+/// Picks the threshold that best separates the histogram into two classes.
+pub fn otsu_threshold(histogram: &[u32; 256]) -> u8 {
+    let total: u64 = histogram.iter().map(|&n| n as u64).sum();
+    let sum_all: u64 = histogram.iter().enumerate().map(|(i, &n)| i as u64 * n as u64).sum();
+
+    let mut best = (0u8, 0.0f64);
+    let mut weight_dark: u64 = 0;
+    let mut sum_dark: u64 = 0;
+
+    for (i, &n) in histogram.iter().enumerate() {
+        weight_dark += n as u64;
+        sum_dark += i as u64 * n as u64;
+
+        let weight_light = total - weight_dark;
+        if weight_dark == 0 || weight_light == 0 {
+            continue;
+        }
+
+        let mean_dark = sum_dark as f64 / weight_dark as f64;
+        let mean_light = (sum_all - sum_dark) as f64 / weight_light as f64;
+        let between = weight_dark as f64 * weight_light as f64 * (mean_dark - mean_light).powi(2);
+
+        if between > best.1 {
+            best = (i as u8, between);
+        }
+    }
+
+    best.0
+}
+
+
+/// Sauvola's method, computed with integral images so that the
+/// cost does not depend on the window size.
+fn sauvola(gray: &GrayImage, window: u32, k: f32) -> Vec<u8> {
+    let (width, height) = gray.dimensions();
+    let stride = (width + 1) as usize;
+
+    // Sums over the rectangle from the origin to (x, y), exclusive.
+    let mut sum = vec![0u64; stride * (height + 1) as usize];
+    let mut sum_sq = vec![0u64; stride * (height + 1) as usize];
+    for y in 0 .. height {
+        let mut row = 0u64;
+        let mut row_sq = 0u64;
+        for x in 0 .. width {
+            let v = gray.get_pixel(x, y).data[0] as u64;
+            row += v;
+            row_sq += v * v;
+            let i = (y as usize + 1) * stride + x as usize + 1;
+            sum[i] = sum[i - stride] + row;
+            sum_sq[i] = sum_sq[i - stride] + row_sq;
+        }
+    }
+
+    let half = window / 2;
+    let mut out = Vec::with_capacity((width * height) as usize);
+    for y in 0 .. height {
+        let top = y.saturating_sub(half) as usize;
+        let bottom = u32::min(y + half + 1, height) as usize;
+        for x in 0 .. width {
+            let left = x.saturating_sub(half) as usize;
+            let right = u32::min(x + half + 1, width) as usize;
+
+            let area = ((bottom - top) * (right - left)) as f64;
+            let rect = |table: &[u64]| {
+                table[bottom * stride + right] + table[top * stride + left]
+                    - table[top * stride + right] - table[bottom * stride + left]
+            };
+
+            let mean = rect(&sum) as f64 / area;
+            let variance = (rect(&sum_sq) as f64 / area - mean * mean).max(0.0);
+            let deviation = variance.sqrt() as f32;
+
+            let t = mean as f32 * (1.0 + k * (deviation / SAUVOLA_RANGE - 1.0));
+            out.push(t.clamp(0.0, 255.0) as u8);
+        }
+    }
+
+    out
+}
+
+
+/// Local contrast: each pixel is compared against the background on
+/// opposite sides of it, across, down and along both diagonals, where the
+/// background on a side is the lightest pixel within `radius`. A thin line
+/// has background on both sides in at least one of those directions, even
+/// where it crosses another line or runs close to text, while a pixel inside
+/// a shaded band has the band's own color on one side in every direction.
+fn contrast(gray: &GrayImage, radius: u32, min_contrast: u8) -> Vec<u8> {
+    let (width, height) = gray.dimensions();
+    let data: &[u8] = gray;
+
+    let mut background = vec![0u8; data.len()];
+    for &(dx, dy) in &[(1, 0), (0, 1), (1, 1), (1, -1)] {
+        let before = lightest_along(data, width, height, (-dx, -dy), radius);
+        let after = lightest_along(data, width, height, (dx, dy), radius);
+        for i in 0 .. data.len() {
+            background[i] = u8::max(background[i], u8::min(before[i], after[i]));
+        }
+    }
+
+    background.iter().map(|&b| b.saturating_sub(min_contrast)).collect()
+}
+
+
+/// For each pixel, the lightest of the `radius` pixels stepping away
+/// from it by `step`, stopping at the edge of the image.
+fn lightest_along(data: &[u8], width: u32, height: u32, step: (i64, i64), radius: u32) -> Vec<u8> {
+    let (w, h) = (width as i64, height as i64);
+    let mut out = vec![0u8; data.len()];
+
+    for y in 0 .. h {
+        for x in 0 .. w {
+            let mut lightest = 0;
+            for d in 1 .. radius as i64 + 1 {
+                let sx = (x + d * step.0).max(0).min(w - 1);
+                let sy = (y + d * step.1).max(0).min(h - 1);
+                lightest = u8::max(lightest, data[(sy * w + sx) as usize]);
+            }
+            out[(y * w + x) as usize] = lightest;
+        }
+    }
+
+    out
+}
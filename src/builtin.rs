@@ -0,0 +1,395 @@
+//! A pure-Rust recognizer that needs no external OCR engine.
+//!
+//! Glyphs are segmented out of the cell as connected regions of ink, then
+//! matched against templates rendered from the built-in bitmap font in
+//! `font` and from the outlines of a real typeface in `sans`. Glyphs that
+//! match nothing closely are read as '?'. Everything the recognizer knows
+//! is compiled into the crate. DejaVu Sans draws `l` and `I` alike, so
+//! the two cannot be told apart in it.
+
+use image::{DynamicImage, GenericImage};
+
+use binarize::{histogram, otsu_threshold};
+use font::{self, CAP_HEIGHT};
+use ocr::{OcrEngine, Recognition};
+use sans;
+
+use std::error::Error;
+
+/// Width of the grid at which glyphs are compared against templates.
+const GRID_WIDTH: u32 = 10;
+
+/// Height of the grid at which glyphs are compared against templates.
+const GRID_HEIGHT: u32 = 14;
+
+/// Subsamples taken along each axis of a single grid square.
+const GRID_SUBSAMPLES: u32 = 4;
+
+/// The minimum difference between the darkest and lightest pixel
+/// for a cell to be considered to contain anything.
+const MIN_CONTRAST: u8 = 48;
+
+/// The fraction of a row or column that must be ink for it to be
+/// considered a leftover piece of gridline rather than text.
+const GRIDLINE_INK_FRACTION: f32 = 0.9;
+
+/// The minimum number of pixels of ink in a glyph for it to be kept.
+const MIN_GLYPH_AREA: u32 = 2;
+
+/// A horizontal gap between glyphs wider than this fraction
+/// of the cap height is read as a space.
+const SPACE_GAP_RATIO: f32 = 0.6;
+
+/// How strongly glyph placement relative to the text line affects matching.
+const POSITION_WEIGHT: f32 = 0.5;
+
+/// How strongly glyph aspect ratio affects matching.
+const ASPECT_WEIGHT: f32 = 0.15;
+
+/// Glyphs further than this from every template are not read.
+const MAX_MATCH_DISTANCE: f32 = 0.45;
+
+/// The size, in pixels to the em, at which `sans` templates are drawn.
+const SANS_TEMPLATE_SIZE: f32 = 48.0;
+
+/// The fraction of a pixel that must be covered to count as ink.
+const SANS_INK_COVERAGE: f32 = 0.5;
+
+
+/// An inclusive rectangle of pixels.
+#[derive(Clone, Copy, Debug)]
+struct BoundingBox {
+    left: u32,
+    top: u32,
+    right: u32,
+    bottom: u32,
+}
+
+impl BoundingBox {
+    fn width(&self) -> u32 {
+        self.right - self.left + 1
+    }
+
+    fn height(&self) -> u32 {
+        self.bottom - self.top + 1
+    }
+
+    fn union(&self, other: &BoundingBox) -> BoundingBox {
+        BoundingBox {
+            left: u32::min(self.left, other.left),
+            top: u32::min(self.top, other.top),
+            right: u32::max(self.right, other.right),
+            bottom: u32::max(self.bottom, other.bottom),
+        }
+    }
+}
+
+
+/// The features of a glyph that are compared during matching.
+struct Features {
+    /// Ink coverage of each grid square, from 0.0 to 1.0.
+    grid: Vec<f32>,
+    /// Top of the glyph, in cap heights below the top of the text line.
+    top: f32,
+    /// Bottom of the glyph, in cap heights below the top of the text line.
+    bottom: f32,
+    /// Width divided by height.
+    aspect: f32,
+}
+
+impl Features {
+    /// Computes features for the ink inside `bbox`, given the top of the
+    /// text line and its cap height.
+    fn new<F>(is_ink: F, bbox: &BoundingBox, line_top: u32, cap_height: u32) -> Features
+        where F: Fn(u32, u32) -> bool
+    {
+        let (width, height) = (bbox.width(), bbox.height());
+        let samples_x = GRID_WIDTH * GRID_SUBSAMPLES;
+        let samples_y = GRID_HEIGHT * GRID_SUBSAMPLES;
+
+        let mut grid = vec![0.0; (GRID_WIDTH * GRID_HEIGHT) as usize];
+        for sy in 0 .. samples_y {
+            let y = bbox.top + (sy * height) / samples_y;
+            for sx in 0 .. samples_x {
+                let x = bbox.left + (sx * width) / samples_x;
+                if is_ink(x, y) {
+                    let gx = sx / GRID_SUBSAMPLES;
+                    let gy = sy / GRID_SUBSAMPLES;
+                    grid[(gy * GRID_WIDTH + gx) as usize] += 1.0;
+                }
+            }
+        }
+
+        let per_square = (GRID_SUBSAMPLES * GRID_SUBSAMPLES) as f32;
+        for square in grid.iter_mut() {
+            *square /= per_square;
+        }
+
+        let cap_height = cap_height as f32;
+        Features {
+            grid,
+            top: (bbox.top as f32 - line_top as f32) / cap_height,
+            bottom: (bbox.bottom as f32 + 1.0 - line_top as f32) / cap_height,
+            aspect: width as f32 / height as f32,
+        }
+    }
+
+    /// How different two glyphs are. Zero means identical.
+    fn distance(&self, other: &Features) -> f32 {
+        let grid: f32 = self.grid.iter()
+            .zip(other.grid.iter())
+            .map(|(a, b)| (a - b).abs())
+            .sum::<f32>() / self.grid.len() as f32;
+
+        let position = (self.top - other.top).abs() + (self.bottom - other.bottom).abs();
+        let aspect = (self.aspect / other.aspect).ln().abs();
+
+        grid + POSITION_WEIGHT * position + ASPECT_WEIGHT * aspect
+    }
+}
+
+
+/// A font glyph prepared for matching.
+struct Template {
+    ch: char,
+    features: Features,
+}
+
+
+/// Recognizes cells by matching glyphs against the built-in font.
+pub struct BuiltinEngine {
+    templates: Vec<Template>,
+}
+
+impl BuiltinEngine {
+    pub fn new() -> BuiltinEngine {
+        let mut templates = Vec::new();
+
+        for glyph in font::GLYPHS {
+            let is_ink = |x: u32, y: u32| glyph.is_set(x, y);
+            let bbox = match ink_bounds(&is_ink, font::GLYPH_WIDTH, font::GLYPH_HEIGHT) {
+                Some(bbox) => bbox,
+                None => continue,
+            };
+
+            templates.push(Template {
+                ch: glyph.ch,
+                features: Features::new(is_ink, &bbox, 0, CAP_HEIGHT),
+            });
+        }
+
+        let scale = SANS_TEMPLATE_SIZE / sans::UNITS_PER_EM;
+        let line_top = ((sans::ASCENT - sans::CAP_HEIGHT) as f32 * scale).round() as u32;
+        let cap_height = (sans::CAP_HEIGHT as f32 * scale).round() as u32;
+        for outline in sans::OUTLINES {
+            let coverage = sans::render(&outline.ch.to_string(), SANS_TEMPLATE_SIZE);
+            let is_ink = |x: u32, y: u32| coverage.at(x, y) >= SANS_INK_COVERAGE;
+            let bbox = match ink_bounds(&is_ink, coverage.width, coverage.height) {
+                Some(bbox) => bbox,
+                None => continue,
+            };
+
+            templates.push(Template {
+                ch: outline.ch,
+                features: Features::new(is_ink, &bbox, line_top, cap_height),
+            });
+        }
+
+        BuiltinEngine { templates }
+    }
+
+    /// Finds the character that best matches the given features,
+    /// along with a confidence in that match. A glyph that is not close
+    /// enough to any template is read as '?' with no confidence.
+    fn classify(&self, features: &Features) -> (char, f32) {
+        let mut best = (' ', f32::INFINITY);
+        for template in &self.templates {
+            let distance = features.distance(&template.features);
+            if distance < best.1 {
+                best = (template.ch, distance);
+            }
+        }
+
+        if best.1 > MAX_MATCH_DISTANCE {
+            ('?', 0.0)
+        } else {
+            (best.0, 1.0 - best.1)
+        }
+    }
+}
+
+impl Default for BuiltinEngine {
+    fn default() -> BuiltinEngine {
+        BuiltinEngine::new()
+    }
+}
+
+impl OcrEngine for BuiltinEngine {
+    fn recognize(&mut self, cell: &DynamicImage) -> Result<Recognition, Box<Error>> {
+        let (width, height) = cell.dimensions();
+        if width < 3 || height < 3 {
+            return Ok(Recognition::empty());
+        }
+
+        let ink = match binarize(cell) {
+            Some(ink) => ink,
+            None => return Ok(Recognition::empty()),
+        };
+        let is_ink = |x: u32, y: u32| ink[(y * width + x) as usize];
+
+        let glyphs = segment_glyphs(&ink, width, height);
+        if glyphs.is_empty() {
+            return Ok(Recognition::empty());
+        }
+
+        // The text line runs from the highest ink down to the most
+        // common baseline among full-height glyphs.
+        let line_top = glyphs.iter().map(|g| g.top).min().unwrap();
+        let tallest = glyphs.iter().map(|g| g.height()).max().unwrap();
+        let mut bottoms: Vec<u32> = glyphs.iter()
+            .filter(|g| g.height() * 2 >= tallest)
+            .map(|g| g.bottom)
+            .collect();
+        bottoms.sort();
+        let baseline = bottoms[bottoms.len() / 2];
+        let cap_height = u32::max(baseline + 1 - line_top, 1);
+
+        let mut text = String::new();
+        let mut confidence = 0.0;
+        let mut prev_right: Option<u32> = None;
+
+        for glyph in &glyphs {
+            if let Some(right) = prev_right {
+                let gap = glyph.left.saturating_sub(right + 1);
+                if gap as f32 > SPACE_GAP_RATIO * cap_height as f32 {
+                    text.push(' ');
+                }
+            }
+            prev_right = Some(glyph.right);
+
+            let features = Features::new(is_ink, glyph, line_top, cap_height);
+            let (ch, glyph_confidence) = self.classify(&features);
+            text.push(ch);
+            confidence += glyph_confidence;
+        }
+
+        Ok(Recognition::new(text, confidence / glyphs.len() as f32))
+    }
+}
+
+
+/// Separates ink from background with Otsu's method.
+/// Returns `None` if the cell is blank.
+fn binarize(cell: &DynamicImage) -> Option<Vec<bool>> {
+    let gray = cell.to_luma();
+    let histogram = histogram(&gray);
+
+    let darkest = histogram.iter().position(|&n| n > 0).unwrap_or(0);
+    let lightest = histogram.iter().rposition(|&n| n > 0).unwrap_or(0);
+    if lightest - darkest < MIN_CONTRAST as usize {
+        return None;
+    }
+
+    let threshold = otsu_threshold(&histogram);
+    Some(gray.pixels().map(|px| px.data[0] <= threshold).collect())
+}
+
+
+/// Finds the bounding box of all ink in a `width` by `height` area.
+fn ink_bounds<F>(is_ink: &F, width: u32, height: u32) -> Option<BoundingBox>
+    where F: Fn(u32, u32) -> bool
+{
+    let mut bounds: Option<BoundingBox> = None;
+    for y in 0 .. height {
+        for x in 0 .. width {
+            if is_ink(x, y) {
+                let px = BoundingBox { left: x, top: y, right: x, bottom: y };
+                bounds = Some(bounds.map_or(px, |b| b.union(&px)));
+            }
+        }
+    }
+    bounds
+}
+
+
+/// Splits the ink in a cell into glyphs, ordered left to right.
+fn segment_glyphs(ink: &[bool], width: u32, height: u32) -> Vec<BoundingBox> {
+    let mut ink = ink.to_vec();
+    let idx = |x: u32, y: u32| (y * width + x) as usize;
+
+    // Gridline remnants along the cell edge span the whole cell.
+    for y in 0 .. height {
+        let count = (0 .. width).filter(|&x| ink[idx(x, y)]).count();
+        if count as f32 >= GRIDLINE_INK_FRACTION * width as f32 {
+            for x in 0 .. width {
+                ink[idx(x, y)] = false;
+            }
+        }
+    }
+    for x in 0 .. width {
+        let count = (0 .. height).filter(|&y| ink[idx(x, y)]).count();
+        if count as f32 >= GRIDLINE_INK_FRACTION * height as f32 {
+            for y in 0 .. height {
+                ink[idx(x, y)] = false;
+            }
+        }
+    }
+
+    // Collect 8-connected blobs of ink.
+    let mut seen = vec![false; ink.len()];
+    let mut blobs = Vec::<(BoundingBox, u32)>::new();
+    let mut stack = Vec::<(u32, u32)>::new();
+
+    for y in 0 .. height {
+        for x in 0 .. width {
+            if !ink[idx(x, y)] || seen[idx(x, y)] {
+                continue;
+            }
+
+            let mut bbox = BoundingBox { left: x, top: y, right: x, bottom: y };
+            let mut area = 0;
+            seen[idx(x, y)] = true;
+            stack.push((x, y));
+
+            while let Some((cx, cy)) = stack.pop() {
+                area += 1;
+                bbox = bbox.union(&BoundingBox { left: cx, top: cy, right: cx, bottom: cy });
+
+                for ny in cy.saturating_sub(1) .. u32::min(cy + 2, height) {
+                    for nx in cx.saturating_sub(1) .. u32::min(cx + 2, width) {
+                        if ink[idx(nx, ny)] && !seen[idx(nx, ny)] {
+                            seen[idx(nx, ny)] = true;
+                            stack.push((nx, ny));
+                        }
+                    }
+                }
+            }
+
+            blobs.push((bbox, area));
+        }
+    }
+
+    // Blobs stacked above one another, like the dot on an 'i',
+    // belong to the same glyph.
+    blobs.sort_by_key(|&(b, _)| b.left);
+    let mut glyphs = Vec::<(BoundingBox, u32)>::new();
+    for (blob, area) in blobs {
+        let merge = match glyphs.last() {
+            Some(&(last, _)) => blob.left <= last.right,
+            None => false,
+        };
+
+        if merge {
+            let (last, last_area) = glyphs.pop().unwrap();
+            glyphs.push((last.union(&blob), last_area + area));
+        } else {
+            glyphs.push((blob, area));
+        }
+    }
+
+    // Whatever is still tiny is noise.
+    glyphs.into_iter()
+        .filter(|&(_, area)| area >= MIN_GLYPH_AREA)
+        .map(|(glyph, _)| glyph)
+        .collect()
+}
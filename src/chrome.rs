@@ -0,0 +1,233 @@
+//! Application chrome inside the grid of a screenshotted table.
+//!
+//! Toolbars and scrollbars lie outside the grid, and are cropped away
+//! along with the rest of the image around it. Spreadsheets add more
+//! inside: the grid goes on past the data, and the columns and rows are
+//! labeled A, B, C and 1, 2, 3 in headers that look just like cells.
+
+use std::collections::HashMap;
+
+use image::{DynamicImage, GenericImage, Rgba};
+
+use ocr::OcrEngine;
+use table::Table;
+use {crop_cell, inset, Cell};
+
+/// The fraction of header labels that must read as the expected sequence.
+/// Some are misread, and the last column may be cut off.
+const MIN_LABEL_MATCHES: f32 = 0.75;
+
+/// How far apart two channels of a header's fill may be for it to be gray,
+/// and how far any channel may be from the other headers' fill.
+const MAX_FILL_DIFFERENCE: u8 = 12;
+
+/// Header fills are light, so their labels can be read.
+const MIN_FILL_LUMA: u8 = 160;
+
+/// The row of column letters is at most this many times as tall as most rows.
+const MAX_HEADER_HEIGHT: f32 = 1.25;
+
+/// Labels that the engine is less sure of than this are taken as unread.
+const MIN_LABEL_CONFIDENCE: f32 = 0.5;
+
+
+/// Removes the spreadsheet's column letters from the top row and its row
+/// numbers from the left column, if the table starts with both.
+///
+/// Headers are found by how they look, whatever font the spreadsheet uses:
+/// both share one light gray fill that the data does not have, the column
+/// of row numbers is narrower than most and the row of letters is no
+/// taller than most. An `engine` that reads ink must also find the labels
+/// counting up, so labels it cannot read, or is unsure of, do not count
+/// against them.
+pub fn strip_sheet_headers(img: &DynamicImage, table: Table, engine: Option<&mut OcrEngine>) -> Table {
+    if table.rows() < 3 || table.cols() < 3 || !looks_like_headers(img, &table) {
+        return table;
+    }
+
+    if let Some(engine) = engine.filter(|e| e.reads_ink()) {
+        let mut read = |cell: &Cell| {
+            engine.recognize(&crop_cell(img, cell)).ok()
+                .filter(|r| r.confidence >= MIN_LABEL_CONFIDENCE)
+                .map(|r| r.text)
+                .unwrap_or_default()
+        };
+
+        // The corner is left out of both, since it is blank or belongs to either.
+        // Above merged title rows, the letters' gridlines are too short to be
+        // found, so a cell may hold several letters and the stubs between them.
+        // Each letter is read across its own column instead, inside the stubs.
+        let letters: Vec<Option<u32>> = (1 .. table.cols()).map(|col| {
+            let top = table.covering(0, col)?;
+            let column = table.cells().iter().find(|c| c.col == col && c.col_span == 1)?;
+            let (inset_x, _) = inset(column.width, column.height);
+            let cell = Cell { x: column.x + inset_x, width: column.width - 2 * inset_x, ..*top };
+            column_number(label(&read(&cell)))
+        }).collect();
+        let numbers: Vec<Option<u32>> = (1 .. table.rows())
+            .filter_map(|row| table.get(row, 0))
+            .map(|cell| if cell.row_span == 1 { label(&read(cell)).parse().ok() } else { None })
+            .collect();
+
+        if contradicts(&letters) || contradicts(&numbers) {
+            return table;
+        }
+    }
+    table.slice(1 .. table.rows(), 1 .. table.cols())
+}
+
+
+/// Whether the top row and left column of the table look like a
+/// spreadsheet's headers, from their fill and size alone.
+fn looks_like_headers(img: &DynamicImage, table: &Table) -> bool {
+    let is_header = |cell: &Cell| (cell.row == 0) != (cell.col == 0);
+    let headers: Vec<&Cell> = table.cells().iter().filter(|c| is_header(c)).collect();
+    let data: Vec<&Cell> = table.cells().iter().filter(|c| c.row > 0 && c.col > 0).collect();
+
+    // Every header has the same light gray fill.
+    let fill = match headers.first() {
+        Some(cell) => fill_color(img, cell),
+        None => return false,
+    };
+    let same = |a: Rgba<u8>, b: Rgba<u8>| (0 .. 3).all(|i| channel_difference(a, b, i) <= MAX_FILL_DIFFERENCE);
+    let (min, max) = (fill.data[.. 3].iter().min().unwrap(), fill.data[.. 3].iter().max().unwrap());
+    if max - min > MAX_FILL_DIFFERENCE || *min < MIN_FILL_LUMA {
+        return false;
+    }
+    if !headers.iter().all(|cell| same(fill_color(img, cell), fill)) {
+        return false;
+    }
+
+    // Zebra stripes may share the fill, but most of the data does not.
+    if data.iter().filter(|cell| same(fill_color(img, cell), fill)).count() * 2 >= data.len() {
+        return false;
+    }
+
+    // Row numbers need less room than most data, and column letters no
+    // more height. Sizes are taken from cells that are not merged, and
+    // rows at the edge of the grid may take in some of the margin.
+    let col_widths: HashMap<u32, u32> = table.cells().iter()
+        .filter(|c| c.col_span == 1).map(|c| (c.col, c.width)).collect();
+    let row_heights: HashMap<u32, u32> = table.cells().iter()
+        .filter(|c| c.row_span == 1).map(|c| (c.row, c.height)).collect();
+    match (col_widths.get(&0), row_heights.get(&0)) {
+        (Some(&width), Some(&height)) => {
+            width < median(col_widths.iter().filter(|e| *e.0 > 0).map(|e| *e.1))
+                && height as f32 <= median(row_heights.iter().filter(|e| *e.0 > 0).map(|e| *e.1)) as f32 * MAX_HEADER_HEIGHT
+        }
+        _ => false,
+    }
+}
+
+
+/// The most common color inside the cell, ignoring its gridlines.
+/// Colors are compared coarsely, so that noise and compression
+/// artifacts do not split up the fill.
+fn fill_color(img: &DynamicImage, cell: &Cell) -> Rgba<u8> {
+    let (inset_x, inset_y) = inset(cell.width, cell.height);
+    let mut counts: HashMap<[u8; 3], (u32, [u32; 3])> = HashMap::new();
+    for y in cell.y + inset_y .. cell.y + cell.height - inset_y {
+        for x in cell.x + inset_x .. cell.x + cell.width - inset_x {
+            let px = img.get_pixel(x, y).data;
+            let entry = counts.entry([px[0] >> 3, px[1] >> 3, px[2] >> 3]).or_insert((0, [0; 3]));
+            entry.0 += 1;
+            for (sum, &value) in entry.1.iter_mut().zip(&px[.. 3]) {
+                *sum += value as u32;
+            }
+        }
+    }
+
+    // The mean of the pixels in the most common bucket.
+    match counts.values().max_by_key(|entry| entry.0) {
+        Some(&(n, sum)) => Rgba([(sum[0] / n) as u8, (sum[1] / n) as u8, (sum[2] / n) as u8, 255]),
+        None => Rgba([255, 255, 255, 255]),
+    }
+}
+
+
+fn channel_difference(a: Rgba<u8>, b: Rgba<u8>, i: usize) -> u8 {
+    (a.data[i] as i16 - b.data[i] as i16).unsigned_abs() as u8
+}
+
+
+fn median<I: Iterator<Item = u32>>(values: I) -> u32 {
+    let mut values: Vec<u32> = values.collect();
+    values.sort();
+    values.get(values.len() / 2).cloned().unwrap_or(0)
+}
+
+
+/// Removes rows and columns without any ink from the edges of the table,
+/// such as the empty cells that a spreadsheet shows past its data.
+pub fn trim_empty(features: &DynamicImage, table: Table) -> Table {
+    let black = Rgba([0, 0, 0, 255]);
+    let has_ink: Vec<bool> = table.cells().iter().map(|cell| {
+        let (inset_x, inset_y) = inset(cell.width, cell.height);
+        (cell.y + inset_y .. cell.y + cell.height - inset_y).any(|y| {
+            (cell.x + inset_x .. cell.x + cell.width - inset_x).any(|x| features.get_pixel(x, y) != black)
+        })
+    }).collect();
+
+    let row_is_empty = |row: u32| table.cells().iter().zip(&has_ink)
+        .all(|(cell, &ink)| !ink || row < cell.row || row >= cell.row + cell.row_span);
+    let col_is_empty = |col: u32| table.cells().iter().zip(&has_ink)
+        .all(|(cell, &ink)| !ink || col < cell.col || col >= cell.col + cell.col_span);
+
+    let (mut top, mut bottom) = (0, table.rows());
+    while top < bottom && row_is_empty(top) { top += 1; }
+    while bottom > top && row_is_empty(bottom - 1) { bottom -= 1; }
+    let (mut left, mut right) = (0, table.cols());
+    while left < right && col_is_empty(left) { left += 1; }
+    while right > left && col_is_empty(right - 1) { right -= 1; }
+
+    let untouched = top == 0 && bottom == table.rows() && left == 0 && right == table.cols();
+    if untouched || top == bottom || left == right {
+        return table;
+    }
+    table.slice(top .. bottom, left .. right)
+}
+
+
+/// A header label without the bits of gridline that are read as punctuation.
+fn label(text: &str) -> &str {
+    text.trim_matches(|c: char| !c.is_alphanumeric())
+}
+
+
+/// The number of a spreadsheet column from its letters, so `A` is 1,
+/// `Z` is 26 and `AA` is 27.
+fn column_number(letters: &str) -> Option<u32> {
+    if letters.is_empty() || letters.len() > 3 {
+        return None;
+    }
+    letters.chars().try_fold(0, |n, ch| match ch {
+        'A' ..= 'Z' => Some(n * 26 + (ch as u32 - 'A' as u32 + 1)),
+        _ => None,
+    })
+}
+
+
+/// Whether most of the labels were read, but do not count up.
+fn contradicts(labels: &[Option<u32>]) -> bool {
+    let read = labels.iter().filter(|l| l.is_some()).count();
+    read as f32 >= MIN_LABEL_MATCHES * labels.len() as f32 && !is_sequence(labels)
+}
+
+
+/// Whether most of the labels count up by one from some start.
+fn is_sequence(labels: &[Option<u32>]) -> bool {
+    if labels.len() < 2 {
+        return false;
+    }
+
+    // Each label votes for where the sequence would start.
+    let mut votes = HashMap::new();
+    for (i, label) in labels.iter().enumerate() {
+        if let Some(n) = *label {
+            *votes.entry(n as i64 - i as i64).or_insert(0) += 1;
+        }
+    }
+
+    let best = votes.values().cloned().max().unwrap_or(0);
+    best as f32 >= MIN_LABEL_MATCHES * labels.len() as f32
+}
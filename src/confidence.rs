@@ -0,0 +1,68 @@
+//! How far the text of each cell can be trusted.
+//!
+//! The text of a cell is wrong if the cell was found in the wrong place,
+//! or if its contents were misread. Structure confidence scores the first,
+//! by how cleanly the borders of the cell were found, and the engine's own
+//! confidence scores the second. A cell is as trustworthy as both together.
+
+use image::{DynamicImage, GenericImage, Rgba};
+
+use Cell;
+
+/// Pixels on either side of a border that are looked at along with it,
+/// since a gridline can sit just outside the cell.
+const BORDER_SLACK_PX: u32 = 1;
+
+
+/// How cleanly the borders of a cell were found, from 0.0 to 1.0, given
+/// the features and lines it was found in. A border is clean where it runs
+/// along a gridline or through blank space, and not where it cuts through
+/// ink. The worst of the four borders counts, and borders along the edge
+/// of the image are clean.
+pub fn structure(features: &DynamicImage, lines: &DynamicImage, cell: &Cell) -> f32 {
+    let (width, height) = lines.dimensions();
+    let black = Rgba([0, 0, 0, 255]);
+
+    // Each border position is clean if the pixels across it include a
+    // line, or include no ink at all.
+    let clean = |across: &[(u32, u32)]| {
+        across.iter().any(|&(x, y)| lines.get_pixel(x, y) != black)
+            || across.iter().all(|&(x, y)| features.get_pixel(x, y) == black)
+    };
+    let score = |positions: Vec<Vec<(u32, u32)>>| {
+        if positions.is_empty() {
+            return 1.0;
+        }
+        positions.iter().filter(|across| clean(across)).count() as f32 / positions.len() as f32
+    };
+
+    let span = |at: u32, limit: u32| {
+        (at.saturating_sub(BORDER_SLACK_PX) ..= u32::min(at + BORDER_SLACK_PX, limit - 1)).collect::<Vec<u32>>()
+    };
+    let (right, bottom) = (cell.x + cell.width, cell.y + cell.height);
+    let (xs, ys) = (cell.x .. u32::min(right, width), cell.y .. u32::min(bottom, height));
+
+    let horizontal = |y: Option<u32>| match y {
+        Some(y) if y < height => xs.clone().map(|x| span(y, height).into_iter().map(|y| (x, y)).collect()).collect(),
+        _ => Vec::new(),
+    };
+    let vertical = |x: Option<u32>| match x {
+        Some(x) if x < width => ys.clone().map(|y| span(x, width).into_iter().map(|x| (x, y)).collect()).collect(),
+        _ => Vec::new(),
+    };
+
+    let sides = [
+        score(horizontal(cell.y.checked_sub(1))),
+        score(horizontal(Some(bottom))),
+        score(vertical(cell.x.checked_sub(1))),
+        score(vertical(Some(right))),
+    ];
+    sides.iter().cloned().fold(1.0, f32::min)
+}
+
+
+/// The confidence of a cell, from the confidence in its borders
+/// and in the recognition of its text.
+pub fn combine(structure: f32, recognition: f32) -> f32 {
+    structure * recognition
+}
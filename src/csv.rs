@@ -0,0 +1,58 @@
+//! Minimal CSV serialization for recognized cell text.
+
+use std::io::{self, Write};
+
+/// Whether a field must be quoted to survive a round-trip.
+fn needs_quotes(field: &str) -> bool {
+    field.contains([',', '"', '\n', '\r'])
+        || field.starts_with(' ')
+        || field.ends_with(' ')
+}
+
+/// Writes a single field, quoting and escaping as needed.
+fn write_field<W: Write>(w: &mut W, field: &str) -> io::Result<()> {
+    if needs_quotes(field) {
+        write!(w, "\"{}\"", field.replace('"', "\"\""))
+    } else {
+        write!(w, "{}", field)
+    }
+}
+
+/// A failed attempt's weight, written negative as OpenPowerlifting does.
+/// Anything but a positive number, such as a weight already written
+/// negative or text that only parses as infinity or NaN, is left as it is.
+pub fn failed_attempt(field: &str) -> String {
+    match field.trim().parse::<f32>() {
+        Ok(kg) if kg.is_finite() && kg > 0.0 => format!("-{}", field.trim()),
+        _ => field.to_string(),
+    }
+}
+
+/// Writes one record, terminated by a newline.
+pub fn write_record<W: Write>(w: &mut W, fields: &[String]) -> io::Result<()> {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            write!(w, ",")?;
+        }
+        write_field(w, field)?;
+    }
+    writeln!(w)
+}
+
+/// Writes every row as its own record.
+pub fn write_rows<W: Write>(w: &mut W, rows: &[Vec<String>]) -> io::Result<()> {
+    for row in rows {
+        write_record(w, row)?;
+    }
+    Ok(())
+}
+
+/// Writes every row as a line of tab-separated fields. TSV has no quoting,
+/// so tabs and line breaks within a field become spaces.
+pub fn write_tsv<W: Write>(w: &mut W, rows: &[Vec<String>]) -> io::Result<()> {
+    for row in rows {
+        let fields: Vec<String> = row.iter().map(|f| f.replace(['\t', '\n', '\r'], " ")).collect();
+        writeln!(w, "{}", fields.join("\t"))?;
+    }
+    Ok(())
+}
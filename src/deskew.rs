@@ -0,0 +1,118 @@
+//! Straightening of rotated screenshots, photos and scans.
+//!
+//! Lines are only found when they are axis-aligned, so a table rotated
+//! by even half a degree loses its long gridlines. The skew is estimated
+//! by projecting the ink onto the vertical axis at a range of candidate
+//! angles, a Hough accumulator restricted to near-horizontal lines: at
+//! the right angle, every row of gridline or text falls into a few bins
+//! and the profile is as sharp as it can be.
+
+use image::DynamicImage;
+
+use binarize;
+use geometry;
+
+/// The largest skew, in degrees, that is looked for in either direction.
+const MAX_SKEW_DEGREES: f32 = 5.0;
+
+/// The step between candidate angles in the first, coarse pass.
+const COARSE_STEP_DEGREES: f32 = 0.1;
+
+/// The step between candidate angles when refining the best coarse angle.
+const FINE_STEP_DEGREES: f32 = 0.01;
+
+/// Smaller skews are left alone, since resampling blurs the image
+/// more than the skew would hurt.
+const MIN_SKEW_DEGREES: f32 = 0.05;
+
+/// The most ink pixels used to score an angle. Larger images are sampled.
+const MAX_INK_SAMPLES: usize = 200_000;
+
+
+/// Estimates how far the content of the image is rotated counterclockwise,
+/// in degrees. Rotating the image clockwise by this much straightens it.
+pub fn estimate_skew(img: &DynamicImage) -> f32 {
+    let gray = img.to_luma();
+    let (width, height) = gray.dimensions();
+    let threshold = binarize::otsu_threshold(&binarize::histogram(&gray));
+
+    let mut ink = Vec::new();
+    for (x, y, px) in gray.enumerate_pixels() {
+        if px.data[0] <= threshold {
+            ink.push((x as f32, y as f32));
+        }
+    }
+    if ink.is_empty() {
+        return 0.0;
+    }
+    if ink.len() > MAX_INK_SAMPLES {
+        let stride = ink.len().div_ceil(MAX_INK_SAMPLES);
+        ink = ink.into_iter().step_by(stride).collect();
+    }
+
+    let cx = (width as f32 - 1.0) / 2.0;
+    let cy = (height as f32 - 1.0) / 2.0;
+    // Rotated points can fall above or below the image by this much.
+    let overhang = (width as f32 * MAX_SKEW_DEGREES.to_radians().sin()).ceil() as usize + 1;
+    let mut bins = vec![0u32; height as usize + 2 * overhang];
+
+    let mut score = |degrees: f32| {
+        for bin in bins.iter_mut() {
+            *bin = 0;
+        }
+        for &(x, y) in &ink {
+            let (_, ry) = geometry::rotate_point(x, y, cx, cy, -degrees.to_radians());
+            let i = (ry.round() + overhang as f32).max(0.0) as usize;
+            if i < bins.len() {
+                bins[i] += 1;
+            }
+        }
+        bins.iter().map(|&n| n as u64 * n as u64).sum::<u64>()
+    };
+
+    let best = best_angle(&mut score, 0.0, MAX_SKEW_DEGREES, COARSE_STEP_DEGREES);
+    best_angle(&mut score, best, COARSE_STEP_DEGREES, FINE_STEP_DEGREES)
+}
+
+
+/// Tries angles within `span` of `center`, nearest first, so that
+/// ties go to the smaller correction.
+fn best_angle<F: FnMut(f32) -> u64>(score: &mut F, center: f32, span: f32, step: f32) -> f32 {
+    let steps = (span / step).round() as i32;
+    let mut best = (center, score(center));
+
+    for i in 1 .. steps + 1 {
+        for &sign in &[1.0, -1.0] {
+            let degrees = center + sign * i as f32 * step;
+            let s = score(degrees);
+            if s > best.1 {
+                best = (degrees, s);
+            }
+        }
+    }
+
+    best.0
+}
+
+
+/// Rotates the image so that content skewed by `degrees` is level.
+/// Skews too small to be worth resampling leave the image as it is.
+pub fn straighten(img: &DynamicImage, degrees: f32) -> DynamicImage {
+    if degrees.abs() < MIN_SKEW_DEGREES {
+        return img.clone();
+    }
+    let background = geometry::background_color(img);
+    geometry::rotate(img, -degrees.to_radians(), background)
+}
+
+
+/// Estimates the skew of the image and straightens it.
+/// Returns the straightened image and the skew in degrees,
+/// which is zero when the image was left as it is.
+pub fn deskew(img: &DynamicImage) -> (DynamicImage, f32) {
+    let degrees = estimate_skew(img);
+    if degrees.abs() < MIN_SKEW_DEGREES {
+        return (img.clone(), 0.0);
+    }
+    (straighten(img, degrees), degrees)
+}
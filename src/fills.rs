@@ -0,0 +1,215 @@
+//! Filled regions, such as dark header bands.
+//!
+//! Spreadsheets often fill their header row with black or navy and letter
+//! it in white. Every pixel of such a band is darker than the page, so if it
+//! were taken as ink it would look like a stack of horizontal lines. Fills
+//! are found as areas that are mostly dark, unlike rules and strokes.
+//! They are treated as cell background: their outlines, and any light
+//! rules that cut right across them, become lines, and the light text on
+//! them becomes ink.
+
+use image::{DynamicImage, GenericImage};
+
+use geometry;
+
+/// How much darker than the page a pixel must be to be part of a fill.
+/// Pastel shading, on which the text is still dark, is left to the ink.
+const MIN_FILL_CONTRAST: u8 = 128;
+
+/// The side of the square neighborhood that must be mostly dark for a pixel
+/// to be in a fill. Gridlines and dark text on the page cover much less of
+/// it; light text on a fill leaves most of it dark.
+const FILL_WINDOW_PX: u32 = 15;
+
+/// The widest gridline that a fill may take in along each of its edges.
+const MAX_RULE_WIDTH_PX: usize = 2;
+
+/// The fraction of a neighborhood that must be dark.
+const MIN_FILL_DENSITY: f32 = 0.5;
+
+/// The fraction of the outermost rows and columns of a fill that must be
+/// dark. A fill is solid around its edges, while bold text that is dense
+/// enough to pass for a fill is ragged.
+const MIN_EDGE_DENSITY: f32 = 0.9;
+
+
+/// The filled regions of an image.
+pub struct Fills {
+    width: u32,
+    /// Filled regions, including the light text and rules inside them.
+    region: Vec<bool>,
+    /// Outlines of the regions and the rules that divide them.
+    borders: Vec<bool>,
+    /// Pixels darker than the page.
+    dark: Vec<bool>,
+}
+
+impl Fills {
+    /// Whether the pixel lies within a filled region.
+    #[inline]
+    pub fn covers(&self, x: u32, y: u32) -> bool {
+        self.region[(y * self.width + x) as usize]
+    }
+
+    /// Whether the pixel is light text on a fill, which is ink
+    /// once the fill's polarity is corrected.
+    #[inline]
+    pub fn is_inverted_ink(&self, x: u32, y: u32) -> bool {
+        let i = (y * self.width + x) as usize;
+        self.region[i] && !self.dark[i]
+    }
+
+    /// Whether the pixel traces the outline of a fill, or lies on a light
+    /// rule that runs right across one and so divides it into cells.
+    #[inline]
+    pub fn is_border(&self, x: u32, y: u32) -> bool {
+        self.borders[(y * self.width + x) as usize]
+    }
+}
+
+
+/// Finds the filled regions of the image.
+///
+/// Fills in spreadsheets are rectangles made up of whole cells, so each
+/// area that is mostly dark is taken as the rectangle around it, with its
+/// edges moved to where the rows and columns stop being mostly dark.
+pub fn detect(img: &DynamicImage) -> Fills {
+    let (width, height) = img.dimensions();
+    let page = geometry::background_color(img);
+    let cutoff = luma(&page.data).saturating_sub(MIN_FILL_CONTRAST);
+    let dark: Vec<bool> = img.to_rgba().pixels().map(|px| luma(&px.data) <= cutoff).collect();
+
+    let index = |x: u32, y: u32| (y * width + x) as usize;
+    let sums = integral(&dark, width, height);
+    let density = |x: u32, y: u32, w: u32, h: u32| {
+        window_sum(&sums, width, x, y, w, h) as f32 / (w * h) as f32
+    };
+
+    let radius = FILL_WINDOW_PX / 2;
+    let mut dense = Vec::with_capacity(dark.len());
+    for y in 0 .. height {
+        let top = y.saturating_sub(radius);
+        let bottom = u32::min(y + radius + 1, height);
+        for x in 0 .. width {
+            let left = x.saturating_sub(radius);
+            let right = u32::min(x + radius + 1, width);
+            dense.push(density(left, top, right - left, bottom - top) >= MIN_FILL_DENSITY);
+        }
+    }
+
+    let mut region = vec![false; dark.len()];
+    for (left, top, right, bottom) in geometry::bounding_boxes(&dense, width, height) {
+        // The dense area stops short of the edges by up to the radius.
+        let mut left = left.saturating_sub(radius);
+        let mut top = top.saturating_sub(radius);
+        let mut right = u32::min(right + radius, width - 1);
+        let mut bottom = u32::min(bottom + radius, height - 1);
+
+        let is_edge = |x: u32, y: u32, w: u32, h: u32| density(x, y, w, h) >= MIN_EDGE_DENSITY;
+        while top < bottom && !is_edge(left, top, right - left + 1, 1) { top += 1; }
+        while bottom > top && !is_edge(left, bottom, right - left + 1, 1) { bottom -= 1; }
+        while left < right && !is_edge(left, top, 1, bottom - top + 1) { left += 1; }
+        while right > left && !is_edge(right, top, 1, bottom - top + 1) { right -= 1; }
+
+        // Shapes that are not rectangles, such as an L of filled cells,
+        // are left alone rather than filling in the corner.
+        let (w, h) = (right - left + 1, bottom - top + 1);
+        if w < FILL_WINDOW_PX || h < FILL_WINDOW_PX || density(left, top, w, h) < MIN_FILL_DENSITY {
+            continue;
+        }
+
+        for y in top .. bottom + 1 {
+            for x in left .. right + 1 {
+                region[index(x, y)] = true;
+            }
+        }
+    }
+
+    let mut borders = vec![false; region.len()];
+
+    // The outline is just outside the region, where gridlines usually are.
+    for y in 0 .. height {
+        for x in 0 .. width {
+            let i = index(x, y);
+            borders[i] = !region[i]
+                && ((x > 0 && region[i - 1]) || (x + 1 < width && region[i + 1])
+                    || (y > 0 && region[i - width as usize])
+                    || (y + 1 < height && region[i + width as usize]));
+        }
+    }
+
+    // A light rule crosses the region from one side to the other.
+    for x in 0 .. width {
+        mark_gaps(&region, &dark, &mut borders, (0 .. height).map(|y| index(x, y)).collect());
+    }
+    for y in 0 .. height {
+        mark_gaps(&region, &dark, &mut borders, (0 .. width).map(|x| index(x, y)).collect());
+    }
+
+    Fills { width, region, borders, dark }
+}
+
+
+/// Along one row or column of pixels, given by their indices, marks
+/// every stretch of the region that is light from one side to the other,
+/// apart from the gridlines that the region may have taken in at its ends.
+fn mark_gaps(region: &[bool], dark: &[bool], borders: &mut [bool], line: Vec<usize>) {
+    let mut start = 0;
+    while start < line.len() {
+        if !region[line[start]] {
+            start += 1;
+            continue;
+        }
+
+        let mut end = start;
+        while end < line.len() && region[line[end]] {
+            end += 1;
+        }
+
+        let stretch = &line[start .. end];
+        let mut longest = 0;
+        let mut run = 0;
+        for &i in stretch {
+            run = if dark[i] { 0 } else { run + 1 };
+            longest = usize::max(longest, run);
+        }
+
+        if longest > 0 && longest + 2 * MAX_RULE_WIDTH_PX >= stretch.len() {
+            for &i in stretch {
+                borders[i] = true;
+            }
+        }
+        start = end;
+    }
+}
+
+
+/// The integer luma of an RGBA pixel.
+fn luma(data: &[u8; 4]) -> u8 {
+    ((data[0] as u32 * 2126 + data[1] as u32 * 7152 + data[2] as u32 * 722) / 10000) as u8
+}
+
+
+/// Counts of set pixels above and to the left of each position, exclusive,
+/// with one extra row and column.
+fn integral(mask: &[bool], width: u32, height: u32) -> Vec<u32> {
+    let stride = (width + 1) as usize;
+    let mut sums = vec![0u32; stride * (height + 1) as usize];
+    for y in 0 .. height as usize {
+        let mut row = 0;
+        for x in 0 .. width as usize {
+            row += mask[y * width as usize + x] as u32;
+            sums[(y + 1) * stride + x + 1] = sums[y * stride + x + 1] + row;
+        }
+    }
+    sums
+}
+
+
+/// The number of set pixels in a rectangle, given the integral image.
+fn window_sum(sums: &[u32], width: u32, x: u32, y: u32, w: u32, h: u32) -> u32 {
+    let stride = (width + 1) as usize;
+    let (x, y, w, h) = (x as usize, y as usize, w as usize, h as usize);
+    sums[(y + h) * stride + x + w] + sums[y * stride + x]
+        - sums[y * stride + x + w] - sums[(y + h) * stride + x]
+}
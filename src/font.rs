@@ -0,0 +1,112 @@
+//! A small built-in bitmap font.
+//!
+//! Each glyph is 5 pixels wide and 9 pixels tall. Rows 0 through 6 span the
+//! cap height and sit on the baseline; rows 7 and 8 hold descenders.
+//! The font is both rendered into synthetic tables and used as the model
+//! for the built-in recognizer, so the two always agree.
+
+/// Width of every glyph, in font pixels.
+pub const GLYPH_WIDTH: u32 = 5;
+
+/// Height of every glyph including descenders, in font pixels.
+pub const GLYPH_HEIGHT: u32 = 9;
+
+/// Height from the top of a capital letter to the baseline, in font pixels.
+pub const CAP_HEIGHT: u32 = 7;
+
+/// A single character of the font.
+pub struct Glyph {
+    pub ch: char,
+    /// One entry per row. Bit 4 is the leftmost pixel, bit 0 the rightmost.
+    pub rows: [u8; 9],
+}
+
+impl Glyph {
+    /// Whether the pixel at (x, y) is inked.
+    #[inline]
+    pub fn is_set(&self, x: u32, y: u32) -> bool {
+        (self.rows[y as usize] >> (GLYPH_WIDTH - 1 - x)) & 1 == 1
+    }
+}
+
+/// Looks up the glyph for a character, if the font has one.
+pub fn glyph(ch: char) -> Option<&'static Glyph> {
+    GLYPHS.iter().find(|g| g.ch == ch)
+}
+
+/// Every glyph in the font.
+pub static GLYPHS: &[Glyph] = &[
+    Glyph { ch: '0', rows: [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110, 0b00000, 0b00000] },
+    Glyph { ch: '1', rows: [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110, 0b00000, 0b00000] },
+    Glyph { ch: '2', rows: [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111, 0b00000, 0b00000] },
+    Glyph { ch: '3', rows: [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110, 0b00000, 0b00000] },
+    Glyph { ch: '4', rows: [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010, 0b00000, 0b00000] },
+    Glyph { ch: '5', rows: [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110, 0b00000, 0b00000] },
+    Glyph { ch: '6', rows: [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110, 0b00000, 0b00000] },
+    Glyph { ch: '7', rows: [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000, 0b00000, 0b00000] },
+    Glyph { ch: '8', rows: [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110, 0b00000, 0b00000] },
+    Glyph { ch: '9', rows: [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100, 0b00000, 0b00000] },
+    Glyph { ch: 'A', rows: [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001, 0b00000, 0b00000] },
+    Glyph { ch: 'B', rows: [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110, 0b00000, 0b00000] },
+    Glyph { ch: 'C', rows: [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110, 0b00000, 0b00000] },
+    Glyph { ch: 'D', rows: [0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100, 0b00000, 0b00000] },
+    Glyph { ch: 'E', rows: [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111, 0b00000, 0b00000] },
+    Glyph { ch: 'F', rows: [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000, 0b00000, 0b00000] },
+    Glyph { ch: 'G', rows: [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111, 0b00000, 0b00000] },
+    Glyph { ch: 'H', rows: [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001, 0b00000, 0b00000] },
+    Glyph { ch: 'I', rows: [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110, 0b00000, 0b00000] },
+    Glyph { ch: 'J', rows: [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100, 0b00000, 0b00000] },
+    Glyph { ch: 'K', rows: [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001, 0b00000, 0b00000] },
+    Glyph { ch: 'L', rows: [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111, 0b00000, 0b00000] },
+    Glyph { ch: 'M', rows: [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001, 0b00000, 0b00000] },
+    Glyph { ch: 'N', rows: [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001, 0b00000, 0b00000] },
+    Glyph { ch: 'O', rows: [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110, 0b00000, 0b00000] },
+    Glyph { ch: 'P', rows: [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000, 0b00000, 0b00000] },
+    Glyph { ch: 'Q', rows: [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101, 0b00000, 0b00000] },
+    Glyph { ch: 'R', rows: [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001, 0b00000, 0b00000] },
+    Glyph { ch: 'S', rows: [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110, 0b00000, 0b00000] },
+    Glyph { ch: 'T', rows: [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00000] },
+    Glyph { ch: 'U', rows: [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110, 0b00000, 0b00000] },
+    Glyph { ch: 'V', rows: [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00000, 0b00000] },
+    Glyph { ch: 'W', rows: [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010, 0b00000, 0b00000] },
+    Glyph { ch: 'X', rows: [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001, 0b00000, 0b00000] },
+    Glyph { ch: 'Y', rows: [0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00000] },
+    Glyph { ch: 'Z', rows: [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111, 0b00000, 0b00000] },
+    Glyph { ch: 'a', rows: [0b00000, 0b00000, 0b01110, 0b00001, 0b01111, 0b10001, 0b01111, 0b00000, 0b00000] },
+    Glyph { ch: 'b', rows: [0b10000, 0b10000, 0b10110, 0b11001, 0b10001, 0b10001, 0b11110, 0b00000, 0b00000] },
+    Glyph { ch: 'c', rows: [0b00000, 0b00000, 0b01110, 0b10000, 0b10000, 0b10001, 0b01110, 0b00000, 0b00000] },
+    Glyph { ch: 'd', rows: [0b00001, 0b00001, 0b01101, 0b10011, 0b10001, 0b10001, 0b01111, 0b00000, 0b00000] },
+    Glyph { ch: 'e', rows: [0b00000, 0b00000, 0b01110, 0b10001, 0b11111, 0b10000, 0b01110, 0b00000, 0b00000] },
+    Glyph { ch: 'f', rows: [0b00110, 0b01001, 0b01000, 0b11100, 0b01000, 0b01000, 0b01000, 0b00000, 0b00000] },
+    Glyph { ch: 'g', rows: [0b00000, 0b00000, 0b01111, 0b10001, 0b10001, 0b01111, 0b00001, 0b10001, 0b01110] },
+    Glyph { ch: 'h', rows: [0b10000, 0b10000, 0b10110, 0b11001, 0b10001, 0b10001, 0b10001, 0b00000, 0b00000] },
+    Glyph { ch: 'i', rows: [0b00100, 0b00000, 0b01100, 0b00100, 0b00100, 0b00100, 0b01110, 0b00000, 0b00000] },
+    Glyph { ch: 'j', rows: [0b00010, 0b00000, 0b00110, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100] },
+    Glyph { ch: 'k', rows: [0b10000, 0b10000, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b00000, 0b00000] },
+    Glyph { ch: 'l', rows: [0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110, 0b00000, 0b00000] },
+    Glyph { ch: 'm', rows: [0b00000, 0b00000, 0b11010, 0b10101, 0b10101, 0b10001, 0b10001, 0b00000, 0b00000] },
+    Glyph { ch: 'n', rows: [0b00000, 0b00000, 0b10110, 0b11001, 0b10001, 0b10001, 0b10001, 0b00000, 0b00000] },
+    Glyph { ch: 'o', rows: [0b00000, 0b00000, 0b01110, 0b10001, 0b10001, 0b10001, 0b01110, 0b00000, 0b00000] },
+    Glyph { ch: 'p', rows: [0b00000, 0b00000, 0b11110, 0b10001, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000] },
+    Glyph { ch: 'q', rows: [0b00000, 0b00000, 0b01111, 0b10001, 0b10001, 0b10001, 0b01111, 0b00001, 0b00001] },
+    Glyph { ch: 'r', rows: [0b00000, 0b00000, 0b10110, 0b11001, 0b10000, 0b10000, 0b10000, 0b00000, 0b00000] },
+    Glyph { ch: 's', rows: [0b00000, 0b00000, 0b01110, 0b10000, 0b01110, 0b00001, 0b11110, 0b00000, 0b00000] },
+    Glyph { ch: 't', rows: [0b01000, 0b01000, 0b11100, 0b01000, 0b01000, 0b01001, 0b00110, 0b00000, 0b00000] },
+    Glyph { ch: 'u', rows: [0b00000, 0b00000, 0b10001, 0b10001, 0b10001, 0b10011, 0b01101, 0b00000, 0b00000] },
+    Glyph { ch: 'v', rows: [0b00000, 0b00000, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00000, 0b00000] },
+    Glyph { ch: 'w', rows: [0b00000, 0b00000, 0b10001, 0b10001, 0b10101, 0b10101, 0b01010, 0b00000, 0b00000] },
+    Glyph { ch: 'x', rows: [0b00000, 0b00000, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b00000, 0b00000] },
+    Glyph { ch: 'y', rows: [0b00000, 0b00000, 0b10001, 0b10001, 0b10001, 0b01111, 0b00001, 0b10001, 0b01110] },
+    Glyph { ch: 'z', rows: [0b00000, 0b00000, 0b11111, 0b00010, 0b00100, 0b01000, 0b11111, 0b00000, 0b00000] },
+    Glyph { ch: '-', rows: [0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000] },
+    Glyph { ch: '+', rows: [0b00000, 0b00100, 0b00100, 0b11111, 0b00100, 0b00100, 0b00000, 0b00000, 0b00000] },
+    Glyph { ch: '.', rows: [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100, 0b00000, 0b00000] },
+    Glyph { ch: ',', rows: [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100, 0b00100, 0b01000] },
+    Glyph { ch: '/', rows: [0b00000, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b00000, 0b00000, 0b00000] },
+    Glyph { ch: '(', rows: [0b00010, 0b00100, 0b01000, 0b01000, 0b01000, 0b00100, 0b00010, 0b00000, 0b00000] },
+    Glyph { ch: ')', rows: [0b01000, 0b00100, 0b00010, 0b00010, 0b00010, 0b00100, 0b01000, 0b00000, 0b00000] },
+    Glyph { ch: '%', rows: [0b11000, 0b11001, 0b00010, 0b00100, 0b01000, 0b10011, 0b00011, 0b00000, 0b00000] },
+    Glyph { ch: ':', rows: [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000, 0b00000, 0b00000] },
+    Glyph { ch: '\'', rows: [0b00100, 0b00100, 0b01000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000] },
+    Glyph { ch: '&', rows: [0b01100, 0b10010, 0b10100, 0b01000, 0b10101, 0b10010, 0b01101, 0b00000, 0b00000] },
+];
@@ -0,0 +1,120 @@
+//! Geometric helpers shared by the image transforms.
+
+use std::collections::HashMap;
+
+use image::{DynamicImage, GenericImage, ImageBuffer, Rgba, RgbaImage};
+
+
+/// Samples the image at a fractional position with bilinear interpolation.
+/// Positions outside the image take the `fill` color.
+pub fn sample_bilinear(img: &DynamicImage, x: f32, y: f32, fill: Rgba<u8>) -> Rgba<u8> {
+    let (width, height) = img.dimensions();
+    if x < 0.0 || y < 0.0 || x > (width - 1) as f32 || y > (height - 1) as f32 {
+        return fill;
+    }
+
+    let x0 = x.floor() as u32;
+    let y0 = y.floor() as u32;
+    let x1 = u32::min(x0 + 1, width - 1);
+    let y1 = u32::min(y0 + 1, height - 1);
+    let fx = x - x0 as f32;
+    let fy = y - y0 as f32;
+
+    let p00 = img.get_pixel(x0, y0);
+    let p10 = img.get_pixel(x1, y0);
+    let p01 = img.get_pixel(x0, y1);
+    let p11 = img.get_pixel(x1, y1);
+
+    let mut out = [0u8; 4];
+    for (c, value) in out.iter_mut().enumerate() {
+        let top = p00.data[c] as f32 * (1.0 - fx) + p10.data[c] as f32 * fx;
+        let bottom = p01.data[c] as f32 * (1.0 - fx) + p11.data[c] as f32 * fx;
+        *value = (top * (1.0 - fy) + bottom * fy).round() as u8;
+    }
+    Rgba(out)
+}
+
+
+/// The most common color in the image, used to fill uncovered areas.
+/// The corner pixel is not enough, since tables often run to the edge.
+pub fn background_color(img: &DynamicImage) -> Rgba<u8> {
+    let mut counts = HashMap::new();
+    for (_, _, px) in img.pixels() {
+        *counts.entry(px.data).or_insert(0u32) += 1;
+    }
+
+    let data = counts.into_iter()
+        .max_by_key(|&(data, n)| (n, data))
+        .map(|(data, _)| data)
+        .unwrap_or([255, 255, 255, 255]);
+    Rgba(data)
+}
+
+
+/// Rotates a point counterclockwise by `radians` about (cx, cy),
+/// in image coordinates where y points down.
+pub fn rotate_point(x: f32, y: f32, cx: f32, cy: f32, radians: f32) -> (f32, f32) {
+    let (sin, cos) = radians.sin_cos();
+    let dx = x - cx;
+    let dy = y - cy;
+    (cx + dx * cos + dy * sin, cy - dx * sin + dy * cos)
+}
+
+
+/// Rotates the image counterclockwise by `radians` about its center,
+/// keeping its dimensions. Uncovered corners take the `fill` color.
+pub fn rotate(img: &DynamicImage, radians: f32, fill: Rgba<u8>) -> DynamicImage {
+    let (width, height) = img.dimensions();
+    let cx = (width as f32 - 1.0) / 2.0;
+    let cy = (height as f32 - 1.0) / 2.0;
+
+    // Each output pixel pulls from where it came from before the rotation.
+    let out: RgbaImage = ImageBuffer::from_fn(width, height, |x, y| {
+        let (sx, sy) = rotate_point(x as f32, y as f32, cx, cy, -radians);
+        sample_bilinear(img, sx, sy, fill)
+    });
+
+    DynamicImage::ImageRgba8(out)
+}
+
+
+/// The bounding box, as inclusive left, top, right and bottom,
+/// of each 4-connected area of the mask.
+pub fn bounding_boxes(mask: &[bool], width: u32, height: u32) -> Vec<(u32, u32, u32, u32)> {
+    let mut seen = vec![false; mask.len()];
+    let mut boxes = Vec::new();
+    let mut stack = Vec::new();
+
+    for start in 0 .. mask.len() {
+        if !mask[start] || seen[start] {
+            continue;
+        }
+
+        seen[start] = true;
+        stack.push(start);
+        let (mut left, mut top, mut right, mut bottom) = (width, height, 0, 0);
+
+        while let Some(i) = stack.pop() {
+            let (x, y) = (i as u32 % width, i as u32 / width);
+            left = u32::min(left, x);
+            right = u32::max(right, x);
+            top = u32::min(top, y);
+            bottom = u32::max(bottom, y);
+
+            let mut visit = |j: usize| {
+                if mask[j] && !seen[j] {
+                    seen[j] = true;
+                    stack.push(j);
+                }
+            };
+            if x > 0 { visit(i - 1); }
+            if x + 1 < width { visit(i + 1); }
+            if y > 0 { visit(i - width as usize); }
+            if y + 1 < height { visit(i + width as usize); }
+        }
+
+        boxes.push((left, top, right, bottom));
+    }
+
+    boxes
+}
@@ -0,0 +1,119 @@
+//! Minimal JSON serialization for detected tables.
+
+use std::io::{self, Write};
+
+/// A JSON value. Objects keep their keys in the order given.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// An object from its keys and values.
+    pub fn object(fields: Vec<(&str, Value)>) -> Value {
+        Value::Object(fields.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+    }
+
+    /// The value of a key in an object. None for other values.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match *self {
+            Value::Object(ref fields) => fields.iter().find(|f| f.0 == key).map(|f| &f.1),
+            _ => None,
+        }
+    }
+
+    /// Writes the value, indented by two spaces for each level.
+    pub fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        self.write_indented(w, 0)?;
+        writeln!(w)
+    }
+
+    fn write_indented<W: Write>(&self, w: &mut W, level: usize) -> io::Result<()> {
+        let indent = |w: &mut W, level: usize| write!(w, "{:1$}", "", 2 * level);
+        match *self {
+            Value::Null => write!(w, "null"),
+            Value::Bool(b) => write!(w, "{}", b),
+            // JSON has no infinities or NaN.
+            Value::Number(n) if !n.is_finite() => write!(w, "null"),
+            Value::Number(n) => write!(w, "{}", n),
+            Value::String(ref s) => write_string(w, s),
+            Value::Array(ref items) if items.is_empty() => write!(w, "[]"),
+            Value::Array(ref items) => {
+                writeln!(w, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    indent(w, level + 1)?;
+                    item.write_indented(w, level + 1)?;
+                    writeln!(w, "{}", if i + 1 < items.len() { "," } else { "" })?;
+                }
+                indent(w, level)?;
+                write!(w, "]")
+            }
+            Value::Object(ref fields) if fields.is_empty() => write!(w, "{{}}"),
+            Value::Object(ref fields) => {
+                writeln!(w, "{{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    indent(w, level + 1)?;
+                    write_string(w, key)?;
+                    write!(w, ": ")?;
+                    value.write_indented(w, level + 1)?;
+                    writeln!(w, "{}", if i + 1 < fields.len() { "," } else { "" })?;
+                }
+                indent(w, level)?;
+                write!(w, "}}")
+            }
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Value {
+        Value::Bool(b)
+    }
+}
+
+impl From<u32> for Value {
+    fn from(n: u32) -> Value {
+        Value::Number(n as f64)
+    }
+}
+
+impl From<f32> for Value {
+    fn from(n: f32) -> Value {
+        // Through a string, so that 0.1 stays 0.1 rather than 0.10000000149011612.
+        Value::Number(n.to_string().parse().unwrap_or(n as f64))
+    }
+}
+
+impl<'a> From<&'a str> for Value {
+    fn from(s: &'a str) -> Value {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Value {
+        Value::String(s)
+    }
+}
+
+/// Writes a string, quoted and escaped.
+fn write_string<W: Write>(w: &mut W, s: &str) -> io::Result<()> {
+    write!(w, "\"")?;
+    for ch in s.chars() {
+        match ch {
+            '"' => write!(w, "\\\"")?,
+            '\\' => write!(w, "\\\\")?,
+            '\n' => write!(w, "\\n")?,
+            '\r' => write!(w, "\\r")?,
+            '\t' => write!(w, "\\t")?,
+            _ if (ch as u32) < 0x20 => write!(w, "\\u{:04x}", ch as u32)?,
+            _ => write!(w, "{}", ch)?,
+        }
+    }
+    write!(w, "\"")
+}
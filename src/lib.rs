@@ -2,130 +2,420 @@
 
 extern crate image;
 extern crate libc;
-use image::{DynamicImage, GenericImage, Rgba};
+use image::{DynamicImage, GenericImage, ImageBuffer, Rgba};
+pub mod augment;
+mod binarize;
+pub mod builtin;
+mod chrome;
+pub mod confidence;
+pub mod csv;
+pub mod deskew;
 mod ffi;
+mod fills;
+pub mod font;
+mod geometry;
+pub mod json;
 mod matrix;
+pub mod ocr;
+pub mod overlay;
+mod params;
+pub mod perspective;
+mod projection;
+mod regions;
+pub mod report;
+pub mod sans;
+pub mod schema;
+pub mod style;
 mod swt;
+pub mod synth;
+mod table;
+#[cfg(feature = "tesseract")]
+pub mod tesseract;
 
 use std::error::Error;
-use std::path::Path;
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
 
 
 use matrix::*;
+use json::Value;
+use ocr::{EngineKind, OcrEngine};
+use perspective::Homography;
+use report::{Entry, Report};
+use schema::Schema;
 use swt::*;
 
-/// The minimum length of a stretch of pixels that can make up a line.
-const LINE_MIN_LENGTH_PX: u32 = 50;
+pub use binarize::{Binarization, Polarity};
+pub use params::DetectionParams;
+pub use table::{Bounds, Table};
+
+/// Cells with a lower confidence than this are listed for review.
+const DEFAULT_MIN_CONFIDENCE: f32 = 0.8;
+
+/// Pixels along each side of a cell that are not searched for ink,
+/// so that its gridlines are not mistaken for contents.
+const CELL_INSET_PX: u32 = 3;
+
+
+/// How the recognized tables are written.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum OutputFormat {
+    /// The text of each cell, as comma-separated values.
+    #[default]
+    Csv,
+    /// The text of each cell, as tab-separated values.
+    Tsv,
+    /// Every cell with its geometry, text and confidence,
+    /// along with how the image was processed.
+    Json,
+}
 
-/// The fraction of pixels in a span of LINE_MIN_LENGTH_PX pixels
-/// that must be featureful for all to be considered a solid line.
-const LINE_FEATUREFUL_THRESHOLD: f32 = 0.95;
+impl OutputFormat {
+    /// Parses `csv`, `tsv` or `json`.
+    pub fn from_name(name: &str) -> Option<OutputFormat> {
+        match name {
+            "csv" => Some(OutputFormat::Csv),
+            "tsv" => Some(OutputFormat::Tsv),
+            "json" => Some(OutputFormat::Json),
+            _ => None,
+        }
+    }
+}
 
-/// The boundary along the image border that should be ignored for
-/// feature detection.
-const FEATURE_BOUNDARY: u32 = 8;
 
-/// The minimum height of a cell.
-const CELL_MIN_HEIGHT_PX: u32 = 10;
+/// What to do with the image.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Command {
+    /// Recognize the text of every cell and write the tables.
+    Convert,
+    /// Write where each cell is, without recognizing any text.
+    Cells,
+    /// Write the word boxes found by stroke width transform.
+    Words,
+    /// Save the gridlines that cells are found along, as a PNG.
+    Lines,
+    /// Print usage and do nothing else.
+    Help,
+}
 
-/// The minimum width of a cell.
-const CELL_MIN_WIDTH_PX: u32 = 8;
+impl Command {
+    /// Parses `convert`, `cells`, `words`, `lines` or `help`.
+    pub fn from_name(name: &str) -> Option<Command> {
+        match name {
+            "convert" => Some(Command::Convert),
+            "cells" => Some(Command::Cells),
+            "words" => Some(Command::Words),
+            "lines" => Some(Command::Lines),
+            "help" => Some(Command::Help),
+            _ => None,
+        }
+    }
+
+    /// The name that `from_name` parses.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Command::Convert => "convert",
+            Command::Cells => "cells",
+            Command::Words => "words",
+            Command::Lines => "lines",
+            Command::Help => "help",
+        }
+    }
+}
 
 
 /// Passes runtime configiration options.
 pub struct Config {
+    pub command: Command,
+    /// The image to read. Empty for `Command::Help`.
     pub filename: String,
+    /// Path of the file to write. If `None`, the output goes to stdout.
+    pub output: Option<String>,
+    pub format: OutputFormat,
+    /// If set, each cell is also saved into this directory as `{row}-{col}.png`.
+    pub crop_dir: Option<String>,
+    /// If set, the images from each stage of detection are saved into this
+    /// directory: `rectified.png`, `features.png`, `lines.png` and `overlay.png`.
+    pub debug_dir: Option<String>,
+    /// The engine used to recognize the contents of each cell.
+    pub engine: EngineKind,
+    /// How features, lines and cells are detected, including how ink
+    /// is separated from background.
+    pub params: DetectionParams,
+    /// Whether the image is dark on light, light on dark, or to be detected.
+    pub polarity: Polarity,
+    /// Write each table in the image to its own numbered CSV file,
+    /// rather than all of them to one file with a leading table column.
+    pub split_tables: bool,
+    /// If set, the header row of each table is renamed to the canonical
+    /// column names of this schema.
+    pub schema: Option<Schema>,
+    /// If set, cells whose confidence is below `min_confidence` are
+    /// listed in this CSV file, so that only they need checking by hand.
+    pub review: Option<String>,
+    pub min_confidence: f32,
+    /// If set, an HTML page for proofreading is written to this path,
+    /// showing each cell's crop next to its recognized text.
+    pub report: Option<String>,
+    /// Print diagnostics to stderr.
+    pub verbose: bool,
 }
 
 impl Config {
-    pub fn new(mut args: std::env::Args) -> Result<Config, &'static str> {
-        args.next();
+    /// Parses the command line, given with the program name first.
+    /// Errors are messages for the user, naming the option at fault.
+    pub fn new<I: Iterator<Item = String>>(args: I) -> Result<Config, String> {
+        let args: Vec<String> = args.skip(1).collect();
+
+        // Help needs no image, and wins over any mistakes in the rest.
+        if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+            return Config::new(vec![String::new(), Command::Help.name().to_string()].into_iter());
+        }
+        let mut args = args.into_iter();
+
+        let mut command = None;
+        let mut filename = None;
+        let mut output = None;
+        let mut format = OutputFormat::default();
+        let mut crop_dir = None;
+        let mut debug_dir = None;
+        let mut engine = EngineKind::default();
+        let mut params = DetectionParams::default();
+        let mut polarity = Polarity::default();
+        let mut split_tables = false;
+        let mut schema = None;
+        let mut review = None;
+        let mut min_confidence = DEFAULT_MIN_CONFIDENCE;
+        let mut report = None;
+        let mut verbose = false;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-o" | "--output" => output = Some(option_value(&mut args, &arg, "path")?),
+                "-f" | "--format" => {
+                    let name = option_value(&mut args, &arg, "format")?;
+                    format = match OutputFormat::from_name(&name) {
+                        Some(format) => format,
+                        None => return Err(format!("Unknown format {}; use csv, tsv or json.", name)),
+                    };
+                }
+                "--crops" => crop_dir = Some(option_value(&mut args, &arg, "directory")?),
+                "--debug-dir" => debug_dir = Some(option_value(&mut args, &arg, "directory")?),
+                "--engine" => {
+                    let name = option_value(&mut args, &arg, "name")?;
+                    engine = match EngineKind::from_name(&name) {
+                        Some(kind) => kind,
+                        None => return Err(format!("Unknown OCR engine {}.", name)),
+                    };
+                }
+                "--binarize" => params.set("binarization", &option_value(&mut args, &arg, "mode")?)?,
+                "--line-min-length" => params.set("line_min_length_px", &option_value(&mut args, &arg, "length")?)?,
+                "--line-threshold" => params.set("line_featureful_threshold", &option_value(&mut args, &arg, "fraction")?)?,
+                "--feature-boundary" => params.set("feature_boundary", &option_value(&mut args, &arg, "width")?)?,
+                "--cell-min-height" => params.set("cell_min_height_px", &option_value(&mut args, &arg, "height")?)?,
+                "--cell-min-width" => params.set("cell_min_width_px", &option_value(&mut args, &arg, "width")?)?,
+                "--params" => {
+                    let path = option_value(&mut args, &arg, "path")?;
+                    let text = match std::fs::read_to_string(&path) {
+                        Ok(text) => text,
+                        Err(e) => return Err(format!("Could not read parameters file {}: {}.", path, e)),
+                    };
+                    params.set_from_str(&text)?;
+                }
+                "--polarity" => {
+                    let name = option_value(&mut args, &arg, "polarity")?;
+                    polarity = match Polarity::from_name(&name) {
+                        Some(polarity) => polarity,
+                        None => return Err(format!("Unknown polarity {}; use auto, normal or inverted.", name)),
+                    };
+                }
+                "--split-tables" => split_tables = true,
+                "--map-headers" => {
+                    schema.get_or_insert_with(Schema::openpowerlifting);
+                }
+                "--synonyms" => {
+                    let path = option_value(&mut args, &arg, "path")?;
+                    let text = match std::fs::read_to_string(&path) {
+                        Ok(text) => text,
+                        Err(e) => return Err(format!("Could not read synonyms file {}: {}.", path, e)),
+                    };
+                    schema.get_or_insert_with(Schema::openpowerlifting).extend_from_str(&text)?;
+                }
+                "--review" => review = Some(option_value(&mut args, &arg, "path")?),
+                "--min-confidence" => {
+                    min_confidence = match option_value(&mut args, &arg, "number")?.parse::<f32>() {
+                        Ok(n) if (0.0 ..= 1.0).contains(&n) => n,
+                        _ => return Err("--min-confidence needs a number from 0 to 1.".to_string()),
+                    };
+                }
+                "--report" => report = Some(option_value(&mut args, &arg, "path")?),
+                "-v" | "--verbose" => verbose = true,
+                _ if arg.starts_with('-') => return Err(format!("Unknown option {}.", arg)),
+                _ => {
+                    // The command, if any, comes before the image.
+                    if command.is_none() && filename.is_none() {
+                        if let Some(name) = Command::from_name(&arg) {
+                            command = Some(name);
+                            continue;
+                        }
+                    }
+                    if filename.is_some() {
+                        return Err(format!("Too many filename arguments: {}.", arg));
+                    }
+                    filename = Some(arg);
+                }
+            }
+        }
 
-        let filename = match args.next() {
+        let command = command.unwrap_or(Command::Convert);
+        let filename = match filename {
             Some(arg) => arg,
-            None => return Err("Missing filename argument."),
+            None if command == Command::Help => String::new(),
+            None => return Err("Missing filename argument.".to_string()),
         };
 
-        Ok(Config { filename })
+        match command {
+            Command::Help => (),
+            Command::Convert if split_tables && output.is_none() => {
+                return Err("--split-tables needs --output.".to_string());
+            }
+            Command::Convert => (),
+            _ if format == OutputFormat::Json => {
+                return Err(format!("--format json only applies to convert, not {}.", command.name()));
+            }
+            _ if split_tables => {
+                return Err(format!("--split-tables only applies to convert, not {}.", command.name()));
+            }
+            Command::Lines if output.is_none() => {
+                return Err("lines writes a PNG; give its path with --output.".to_string());
+            }
+            _ => (),
+        }
+
+        Ok(Config {
+            command, filename, output, format, crop_dir, debug_dir,
+            engine, params, polarity, split_tables, schema, review, min_confidence, report, verbose,
+        })
     }
 }
 
 
-fn dump(img: &DynamicImage, filename: &str) {
-    let mut file = std::fs::File::create(filename).unwrap();
-    img.save(&mut file, image::PNG).unwrap();
+/// The value that must follow an option, as the path follows `--output`.
+fn option_value<I: Iterator<Item = String>>(args: &mut I, option: &str, what: &str) -> Result<String, String> {
+    args.next().ok_or_else(|| format!("Missing {} after {}.", what, option))
+}
+
+
+fn dump<P: AsRef<Path>>(img: &DynamicImage, path: P) -> Result<(), Box<Error>> {
+    let mut file = File::create(path)?;
+    img.save(&mut file, image::PNG)?;
+    Ok(())
 }
 
 
-/// Determines whether the pixel is sufficiently dark
-/// to be considered part of a line.
-#[inline]
-fn could_be_feature(px: &Rgba<u8>) -> bool {
-    const THRESHOLD: u8 = 130;
-    px.data[0] <= THRESHOLD && px.data[1] <= THRESHOLD && px.data[2] <= THRESHOLD
+/// Saves the rectified image, the features and lines that detection found
+/// in it, and an overlay of the cells and words found on the source image,
+/// into `dir`.
+fn dump_stages(dir: &Path, source: &DynamicImage, img: &DynamicImage, stages: &Stages,
+               tables: &[Table], words: &[Rect]) -> Result<(), Box<Error>> {
+    let words: Vec<Bounds> = words.iter()
+        .map(|w| Bounds { x: w.x.max(0) as u32, y: w.y.max(0) as u32, width: w.width.max(0) as u32, height: w.height.max(0) as u32 })
+        .collect();
+
+    dump(img, dir.join("rectified.png"))?;
+    dump(&stages.features, dir.join("features.png"))?;
+    dump(&stages.lines, dir.join("lines.png"))?;
+    dump(&overlay::draw(source, tables, &words), dir.join("overlay.png"))
 }
 
 
 /// Given an image, return another image where all
 /// pixels that could be features have a magic color,
 /// and where non-features are black.
-fn detect_features(img: &DynamicImage) -> DynamicImage {
-    // Grayscale is used to detect changes in brightness.
-    let gray = img.grayscale();
+/// Also returns the mean darkness threshold that was used.
+fn detect_features(img: &DynamicImage, params: &DetectionParams) -> (DynamicImage, f32) {
+    let (mut features, threshold) = detect_all_features(img, &params.binarization);
+    remove_boundary_features(&mut features, params.feature_boundary);
+    (features, threshold)
+}
+
+
+/// Like `detect_features`, but keeps features along the image border.
+fn detect_all_features(img: &DynamicImage, binarization: &Binarization) -> (DynamicImage, f32) {
+    let ink = binarize::ink(img, binarization);
+    let fills = fills::detect(img);
     let mut features = img.clone();
     let black = Rgba([0, 0, 0, 255]);
     let magic = Rgba([255, 0, 255, 255]);
 
-    // Only keeps pixels below a certain darkness.
-    // Assumes that the text and lines will be black.
-    for (x, y, px) in gray.pixels() {
-        let sub = if could_be_feature(&px) { magic } else { black };
-        features.put_pixel(x, y, sub);
+    // Keeps only the pixels that the binarization counts as ink.
+    // Dark fills are cell background, bordered by their outlines,
+    // and the light text on them is ink.
+    let (width, height) = img.dimensions();
+    for y in 0 .. height {
+        for x in 0 .. width {
+            let is_feature = if fills.is_border(x, y) {
+                true
+            } else if fills.covers(x, y) {
+                fills.is_inverted_ink(x, y)
+            } else {
+                ink.at(x, y)
+            };
+            features.put_pixel(x, y, if is_feature { magic } else { black });
+        }
     }
 
-    let (width, height) = img.dimensions();
-    if width < FEATURE_BOUNDARY || height < FEATURE_BOUNDARY {
-        return features;
+    (features, ink.mean_threshold())
+}
+
+
+/// Clears features within `boundary` pixels of the image border.
+fn remove_boundary_features(features: &mut DynamicImage, boundary: u32) {
+    let (width, height) = features.dimensions();
+    let black = Rgba([0, 0, 0, 255]);
+
+    if width < boundary || height < boundary {
+        return;
     }
 
     // Remove boundary features along the left side.
-    for x in 0 .. u32::min(FEATURE_BOUNDARY, width) {
+    for x in 0 .. u32::min(boundary, width) {
         for y in 0 .. height {
             features.put_pixel(x, y, black);
         }
     }
 
     // Remove boundary features along the right side.
-    for x in (width - FEATURE_BOUNDARY) .. width {
+    for x in (width - boundary) .. width {
         for y in 0 .. height {
             features.put_pixel(x, y, black);
         }
     }
 
     // Remove boundary features on the top.
-    for y in 0 .. u32::min(FEATURE_BOUNDARY, height) {
+    for y in 0 .. u32::min(boundary, height) {
         for x in 0 .. width {
             features.put_pixel(x, y, black);
         }
     }
 
     // Remove boundary features on the bottom.
-    for y in (height - FEATURE_BOUNDARY) .. height {
+    for y in (height - boundary) .. height {
         for x in 0 .. width {
             features.put_pixel(x, y, black);
         }
     }
 
-    features
 }
 
 
-fn is_line_to_right(features: &DynamicImage, x: u32, y: u32) -> bool {
+fn is_line_to_right(features: &DynamicImage, x: u32, y: u32, params: &DetectionParams) -> bool {
     let mut count = 0;
     let black = Rgba([0,0,0,255]);
 
     // Count the number of featureful pixels.
-    for k in x .. (x + LINE_MIN_LENGTH_PX) {
+    for k in x .. (x + params.line_min_length_px) {
         if features.get_pixel(k, y) != black {
             count += 1;
         }
@@ -133,16 +423,16 @@ fn is_line_to_right(features: &DynamicImage, x: u32, y: u32) -> bool {
 
     // If the number of featureful pixels was above a certain threshold,
     // it was probably a line.
-    (count as f32) / (LINE_MIN_LENGTH_PX as f32) >= LINE_FEATUREFUL_THRESHOLD
+    (count as f32) / (params.line_min_length_px as f32) >= params.line_featureful_threshold
 }
 
 
-fn is_line_downward(features: &DynamicImage, x: u32, y: u32) -> bool {
+fn is_line_downward(features: &DynamicImage, x: u32, y: u32, params: &DetectionParams) -> bool {
     let mut count = 0;
     let black = Rgba([0,0,0,255]);
 
     // Count the number of featureful pixels.
-    for k in y .. (y + LINE_MIN_LENGTH_PX) {
+    for k in y .. (y + params.line_min_length_px) {
         if features.get_pixel(x, k) != black {
             count += 1;
         }
@@ -150,12 +440,12 @@ fn is_line_downward(features: &DynamicImage, x: u32, y: u32) -> bool {
 
     // If the number of featureful pixels was above a certain threshold,
     // it was probably a line.
-    (count as f32) / (LINE_MIN_LENGTH_PX as f32) >= LINE_FEATUREFUL_THRESHOLD
+    (count as f32) / (params.line_min_length_px as f32) >= params.line_featureful_threshold
 }
 
 
 /// Reduce features to just those that are probably in lines.
-fn detect_lines(features: &DynamicImage) -> DynamicImage {
+fn detect_lines(features: &DynamicImage, params: &DetectionParams) -> DynamicImage {
     let magic = Rgba([255,0,0,255]);
     let black = Rgba([0,0,0,255]);
     let (width, height) = features.dimensions();
@@ -176,31 +466,30 @@ fn detect_lines(features: &DynamicImage) -> DynamicImage {
         }
 
         // If there is a line to the right, color all those pixels.
-        if x < width - LINE_MIN_LENGTH_PX {
-            if is_line_to_right(features, x, y) {
-                for k in x .. (x + LINE_MIN_LENGTH_PX) {
-                    tmp.put_pixel(k, y, magic);
-                }
+        if x + params.line_min_length_px < width && is_line_to_right(features, x, y, params) {
+            for k in x .. (x + params.line_min_length_px) {
+                tmp.put_pixel(k, y, magic);
             }
         }
 
         // If there is a line downward, color all those pixels.
-        if y < height - LINE_MIN_LENGTH_PX {
-            if is_line_downward(features, x, y) {
-                for k in y .. (y + LINE_MIN_LENGTH_PX) {
-                    tmp.put_pixel(x, k, magic);
-                }
+        if y + params.line_min_length_px < height && is_line_downward(features, x, y, params) {
+            for k in y .. (y + params.line_min_length_px) {
+                tmp.put_pixel(x, k, magic);
             }
         }
     }
 
     // For the benefit of the next phase, extend row lines all the way
-    // to the left.
+    // to the left, unless the boundary leaves nothing to extend them from.
     // FIXME: This is a bad heuristic and should be more robust.
-    for y in 0 .. height {
-        if tmp.get_pixel(FEATURE_BOUNDARY + 1, y) != black {
-            for x in 0 .. (FEATURE_BOUNDARY + 1) {
-                tmp.put_pixel(x, y, magic);
+    let start = params.feature_boundary + 1;
+    if start < width {
+        for y in 0 .. height {
+            if tmp.get_pixel(start, y) != black {
+                for x in 0 .. start {
+                    tmp.put_pixel(x, y, magic);
+                }
             }
         }
     }
@@ -208,147 +497,961 @@ fn detect_lines(features: &DynamicImage) -> DynamicImage {
     tmp
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Cell {
-    /// Row position in the image, with the top row beginning at 0.
+    /// Row position in the table, with the top row beginning at 0.
     pub row: u32,
-    /// Column position in the image, with the left column beginning at 0.
-    /// Column number is for the given row.
-    /// Different rows may have different column counts.
+    /// Column position in the table, with the left column beginning at 0.
+    /// Columns are shared by every row of the `Table`, so a row with
+    /// merged cells skips the column numbers that its cells cover.
     pub col: u32,
 
+    /// Number of rows covered by the Cell. Greater than 1 for merged cells.
+    pub row_span: u32,
+    /// Number of columns covered by the Cell. Greater than 1 for merged cells.
+    pub col_span: u32,
+
     /// Position information for the Cell within the underlying image.
     pub x: u32,
     pub y: u32,
     pub width: u32,
     pub height: u32,
+
+    /// How cleanly the borders of the Cell were found, from 0.0 (cut
+    /// through ink all around) to 1.0 (along gridlines or blank space).
+    pub structure: f32,
 }
 
-fn detect_cells_in_row(acc: &mut Vec<Cell>,
-                       lines: &DynamicImage,
-                       cur_row: u32,
-                       y_top: u32,
-                       y_bottom: u32)
-{
+impl Cell {
+    /// Whether the Cell covers the given row and column.
+    pub fn covers(&self, row: u32, col: u32) -> bool {
+        row >= self.row && row < self.row + self.row_span &&
+        col >= self.col && col < self.col + self.col_span
+    }
+}
+
+
+/// How far in from the left and top of a cell, or of its crop, its contents
+/// start. Dimensions too small to leave anything inside are not inset.
+pub(crate) fn inset(width: u32, height: u32) -> (u32, u32) {
+    let inset = |size: u32| if size > 2 * CELL_INSET_PX { CELL_INSET_PX } else { 0 };
+    (inset(width), inset(height))
+}
+
+
+/// Whether a horizontal line at y crosses most of the image.
+fn is_row_separator(lines: &DynamicImage, y: u32) -> bool {
     let (width, _) = lines.dimensions();
     let black = Rgba([0,0,0,255]);
 
-    // All cells in this row have the same vertical characteristics.
-    let cell_y = y_top;
-    let cell_height = y_bottom - y_top - 1;
+    // The previous phase extended lines all the way to the left.
+    if lines.get_pixel(0, y) != black {
+        return true;
+    }
+
+    // Lines that start further right, beside a merged cell in the
+    // leftmost column, still separate rows elsewhere.
+    let count = (0 .. width).filter(|&x| lines.get_pixel(x, y) != black).count() as u32;
+    count * 2 >= width
+}
+
+
+/// Given an image with only lines, find the vertical extent of each row
+/// as (top, bottom), where the bottom is the separating line.
+fn detect_rows(lines: &DynamicImage, params: &DetectionParams) -> Vec<(u32, u32)> {
+    let (_, height) = lines.dimensions();
+
+    let mut acc = Vec::new();
+
+    // The y-coordinate for the current row.
+    let mut prev_y = 0;
+
+    let mut y = params.cell_min_height_px.saturating_sub(1);
+    while y < height {
+        // If this pixel defines the bottom of a new row,
+        if is_row_separator(lines, y) || y == (height-1) {
+            acc.push((prev_y, y));
+
+            // End of row processing: skip by the minimum cell height.
+            prev_y = y + 1;
+            y += params.cell_min_height_px;
+        } else {
+            // No row found: check the next pixel.
+            y += 1;
+        }
+    }
+
+    // Make a final row with the border wall.
+    if prev_y + params.cell_min_height_px < height {
+        acc.push((prev_y, height - 1));
+    }
+
+    acc
+}
+
+
+/// Finds the x-coordinates at which vertical lines cross
+/// the middle of the row between y_top and y_bottom.
+fn detect_breaks_in_row(lines: &DynamicImage, y_top: u32, y_bottom: u32, params: &DetectionParams) -> Vec<u32> {
+    let (width, _) = lines.dimensions();
+    let black = Rgba([0,0,0,255]);
 
     // Y-position at which to test for vertical lines.
-    let cell_y_median = (cell_y) + (cell_height / 2);
+    let cell_y_median = y_top + (y_bottom - y_top).saturating_sub(1) / 2;
+    let is_line = |x: u32, y: u32| lines.get_pixel(x, y) != black;
 
-    // March to the right. If a line (or boundary) is encountered,
-    // create a new Cell.
-    let mut cur_col: u32 = 0;
-    let mut prev_x = 0;
-    let mut x = CELL_MIN_WIDTH_PX - 1;
+    // Vertical lines also run through these, a quarter of the way
+    // down and up the row, while lines along the middle do not.
+    let quarter = (y_bottom - y_top) / 4;
+    let crosses_row = |x: u32| is_line(x, y_top + quarter) && is_line(x, y_bottom - quarter);
 
-    while x < width {
-        // Line encountered! Make a Cell.
-        if lines.get_pixel(x, cell_y_median) != black {
-            // Use current values to produce a Cell.
-            let cell = Cell {
-                row: cur_row,
-                col: cur_col,
-                x: prev_x,
-                y: cell_y,
-                width: (x - prev_x - 1),
-                height: cell_height,
-            };
+    let mut acc = Vec::new();
+    let mut x = params.cell_min_width_px.saturating_sub(1);
 
-            acc.push(cell);
+    while x < width {
+        if is_line(x, cell_y_median) {
+            // A run as long as a line is a horizontal line through the
+            // middle of the row, such as a strikethrough. It only breaks
+            // the row where vertical lines cross it.
+            let end = (x .. width).find(|&k| !is_line(k, cell_y_median)).unwrap_or(width);
+            if end - x >= params.line_min_length_px {
+                while x < end {
+                    if crosses_row(x) {
+                        acc.push(x);
+                        x += params.cell_min_width_px;
+                    } else {
+                        x += 1;
+                    }
+                }
+                continue;
+            }
 
-            // Update cursor.
-            prev_x = x + 1;
-            cur_col += 1;
+            acc.push(x);
 
-            // New column defined: ignore lines within CELL_MIN_WIDTH_PX.
-            x += CELL_MIN_WIDTH_PX;
+            // New column defined: ignore lines within the minimum cell width.
+            x += params.cell_min_width_px;
         } else {
-            // Check the next pixel for a line.
             x += 1;
         }
     }
 
-    // Make a final cell with the border wall.
-    if prev_x + CELL_MIN_WIDTH_PX < width {
-        let cell = Cell {
-            row: cur_row,
-            col: cur_col,
-            x: prev_x,
-            y: cell_y,
-            width: (width - prev_x - 1),
-            height: cell_height,
-        };
-        acc.push(cell);
+    acc
+}
+
+
+/// Merges the vertical lines found in each row into column separators
+/// shared by the whole table. A separator must be seen in at least
+/// half of the rows; rows that missed it will still be cut by it.
+fn detect_col_separators(breaks_by_row: &[Vec<u32>], params: &DetectionParams) -> Vec<u32> {
+    let mut breaks: Vec<(u32, usize)> = Vec::new();
+    for (row, breaks_in_row) in breaks_by_row.iter().enumerate() {
+        breaks.extend(breaks_in_row.iter().map(|&x| (x, row)));
+    }
+    breaks.sort();
+
+    let mut acc = Vec::new();
+    let mut i = 0;
+    while i < breaks.len() {
+        // Gather breaks that are within the minimum cell width of their neighbor.
+        let mut j = i + 1;
+        while j < breaks.len() && breaks[j].0 - breaks[j - 1].0 <= params.cell_min_width_px {
+            j += 1;
+        }
+
+        let group = &breaks[i .. j];
+        let mut rows: Vec<usize> = group.iter().map(|&(_, row)| row).collect();
+        rows.sort();
+        rows.dedup();
+
+        if rows.len() * 2 >= breaks_by_row.len() {
+            acc.push(group[group.len() / 2].0);
+        }
+
+        i = j;
     }
+
+    acc
+}
+
+
+/// Whether a vertical line runs through most of the row near x.
+/// This tolerates a line that wobbles or has gaps.
+fn has_line_in_row(lines: &DynamicImage, x: u32, y_top: u32, y_bottom: u32) -> bool {
+    const SLACK_PX: u32 = 2;
+    let (width, _) = lines.dimensions();
+    let black = Rgba([0,0,0,255]);
+
+    let left = x.saturating_sub(SLACK_PX);
+    let right = u32::min(x + SLACK_PX, width - 1);
+
+    let count = (y_top .. y_bottom)
+        .filter(|&y| (left ..= right).any(|k| lines.get_pixel(k, y) != black))
+        .count() as u32;
+
+    count * 2 >= y_bottom - y_top
 }
 
-/// Given an image with only lines, get a list of Cells.
-fn detect_cells(lines: &DynamicImage) -> Vec<Cell> {
+
+/// Whether a horizontal line near y runs along most of the span from
+/// x_left to x_right, meaning the cells above and below are separate.
+fn has_line_in_col(lines: &DynamicImage, y: u32, x_left: u32, x_right: u32) -> bool {
+    const SLACK_PX: u32 = 2;
     let (_, height) = lines.dimensions();
     let black = Rgba([0,0,0,255]);
 
-    // The final vector to be returned.
+    let top = y.saturating_sub(SLACK_PX);
+    let bottom = u32::min(y + SLACK_PX, height - 1);
+
+    let count = (x_left .. x_right)
+        .filter(|&x| (top ..= bottom).any(|k| lines.get_pixel(x, k) != black))
+        .count() as u32;
+
+    count * 2 >= x_right - x_left
+}
+
+
+/// Joins each cell to the cell above it when no line separates them,
+/// producing cells that span several rows. The cells must be sorted
+/// by (row, col).
+fn merge_rows(lines: &DynamicImage, rows: &[(u32, u32)], cells: Vec<Cell>) -> Vec<Cell> {
+    let mut acc = Vec::<Cell>::with_capacity(cells.len());
+
+    for cell in cells {
+        if cell.row > 0 {
+            let separator = rows[cell.row as usize - 1].1;
+            if !has_line_in_col(lines, separator, cell.x, cell.x + cell.width) {
+                // Only cells with exactly the same columns can be joined.
+                let above = acc.iter_mut().rev().find(|c| {
+                    c.col == cell.col && c.col_span == cell.col_span &&
+                    c.row + c.row_span == cell.row
+                });
+
+                if let Some(above) = above {
+                    above.height = cell.y + cell.height - above.y;
+                    above.row_span += 1;
+                    continue;
+                }
+            }
+        }
+
+        acc.push(cell);
+    }
+
+    acc
+}
+
+
+/// Given an image with only lines, get the Table of Cells.
+fn detect_cells(lines: &DynamicImage, params: &DetectionParams) -> Table {
+    let (width, _) = lines.dimensions();
+
+    let rows = detect_rows(lines, params);
+    let breaks: Vec<Vec<u32>> = rows.iter()
+        .map(|&(y_top, y_bottom)| detect_breaks_in_row(lines, y_top, y_bottom, params))
+        .collect();
+    let separators = detect_col_separators(&breaks, params);
+
+    // The rightmost column only exists if there is room after the last line.
+    let last_x = separators.last().map_or(0, |&x| x + 1);
+    let has_final_col = last_x + params.cell_min_width_px < width;
+    let cols = separators.len() as u32 + if has_final_col { 1 } else { 0 };
+
     let mut acc = Vec::<Cell>::new();
 
-    // Current row and column information.
-    let mut cur_row: u32 = 0;
+    for (cur_row, &(y_top, y_bottom)) in rows.iter().enumerate() {
+        // All cells in this row have the same vertical characteristics.
+        let cell_y = y_top;
+        let cell_height = (y_bottom - y_top).saturating_sub(1);
 
-    // The y-coordinate for the current row.
-    let mut prev_y = 0;
+        // March to the right. Every separator that crosses this row ends a Cell.
+        let mut cur_col: u32 = 0;
+        let mut prev_x = 0;
 
-    // The previous phase extended lines all the way to the left, so we
-    // need only consider the leftmost column of pixels.
-    let mut y = CELL_MIN_HEIGHT_PX - 1;
-    while y < height {
-        // If this pixel defines the bottom of a new row,
-        if lines.get_pixel(0, y) != black || y == (height-1) {
-            detect_cells_in_row(&mut acc, &lines, cur_row, prev_y, y);
+        for (i, &x) in separators.iter().enumerate() {
+            if !has_line_in_row(lines, x, y_top, y_bottom) {
+                continue;
+            }
 
-            // End of row processing: skip by CELL_MIN_HEIGHT_PX.
-            prev_y = y + 1;
-            y += CELL_MIN_HEIGHT_PX;
-            cur_row += 1;
-        } else {
-            // No row found: check the next pixel.
-            y += 1;
+            acc.push(Cell {
+                row: cur_row as u32,
+                col: cur_col,
+                row_span: 1,
+                col_span: i as u32 + 1 - cur_col,
+                x: prev_x,
+                y: cell_y,
+                width: x.saturating_sub(prev_x + 1),
+                height: cell_height,
+                structure: 1.0,
+            });
+
+            prev_x = x + 1;
+            cur_col = i as u32 + 1;
+        }
+
+        // Make a final cell with the border wall.
+        if prev_x + params.cell_min_width_px < width {
+            acc.push(Cell {
+                row: cur_row as u32,
+                col: cur_col,
+                row_span: 1,
+                col_span: cols - cur_col,
+                x: prev_x,
+                y: cell_y,
+                width: (width - prev_x - 1),
+                height: cell_height,
+                structure: 1.0,
+            });
         }
     }
 
-    // Make a final row with the border wall.
-    if prev_y + CELL_MIN_HEIGHT_PX < height {
-        detect_cells_in_row(&mut acc, &lines, cur_row, prev_y, height - 1);
+    let acc = merge_rows(lines, &rows, acc);
+
+    let row_separators = rows.iter().map(|&(_, y_bottom)| y_bottom).collect();
+    Table::new(row_separators, separators, rows.len() as u32, cols, acc)
+}
+
+
+/// An image prepared for cell detection.
+pub struct Rectified {
+    /// The straightened and flattened image that cells are found in.
+    pub image: DynamicImage,
+    /// The skew that was removed, in degrees counterclockwise.
+    pub skew: f32,
+    /// Maps coordinates in `image` back to the original image.
+    pub to_original: Homography,
+}
+
+
+/// Straightens a rotated image, then warps a table photographed
+/// at an angle so that its outline is a rectangle again.
+pub fn rectify(img: &DynamicImage) -> Rectified {
+    let (straight, skew) = deskew::deskew(img);
+
+    let (width, height) = img.dimensions();
+    let to_original = Homography::rotation((width as f32 - 1.0) / 2.0,
+                                           (height as f32 - 1.0) / 2.0,
+                                           skew.to_radians());
+
+    match perspective::correct(&straight) {
+        Some((image, to_straight)) => {
+            Rectified { image, skew, to_original: to_straight.then(&to_original) }
+        }
+        None => Rectified { image: straight, skew, to_original },
     }
+}
 
-    acc
+
+/// Finds the Table in the image.
+///
+/// Light text on a dark background is inverted first, and the image is
+/// rectified; cell positions refer to the rectified image, and
+/// `Table::to_original` maps them back.
+/// Gridlines are used when there are any, and only the grid they form is
+/// searched, leaving out toolbars and other chrome around it. Tables drawn
+/// without them are split up by the whitespace between rows and columns
+/// instead. If the image holds several tables, the largest is returned.
+pub fn get_cells(img: &DynamicImage) -> Table {
+    get_cells_with(img, &DetectionParams::default())
+}
+
+
+/// Like `get_cells`, but with a choice of how ink is told apart from background.
+pub fn get_cells_with_binarization(img: &DynamicImage, binarization: &Binarization) -> Table {
+    get_cells_with(img, &DetectionParams { binarization: *binarization, ..DetectionParams::default() })
+}
+
+
+/// Like `get_cells`, but with parameters tuned for the image.
+pub fn get_cells_with(img: &DynamicImage, params: &DetectionParams) -> Table {
+    let tables = find_tables(img, params, Polarity::Auto, None, None).1;
+    tables.into_iter()
+        .max_by_key(|table| {
+            let b = table.bounds();
+            b.width as u64 * b.height as u64
+        })
+        .expect("there is always at least one table")
+}
+
+
+/// Finds every Table in the image, in reading order.
+///
+/// Each grid of lines that stands apart from the others is its own Table,
+/// and `Table::bounds` tells where it is. The image is prepared as for
+/// `get_cells`, and an image without separate grids gives a single Table.
+pub fn get_tables(img: &DynamicImage) -> Vec<Table> {
+    get_tables_with(img, &DetectionParams::default())
+}
+
+
+/// Like `get_tables`, but with parameters tuned for the image.
+pub fn get_tables_with(img: &DynamicImage, params: &DetectionParams) -> Vec<Table> {
+    find_tables(img, params, Polarity::Auto, None, None).1
+}
+
+
+/// The features and lines that detection found in the rectified image.
+/// Separate tables are found each in its own crop, so their features and
+/// lines are those of the crop, put back where it was in the image.
+struct Stages {
+    features: DynamicImage,
+    lines: DynamicImage,
+}
+
+impl Stages {
+    /// Stages to be filled in by detection.
+    fn empty() -> Stages {
+        let empty = DynamicImage::new_rgba8(0, 0);
+        Stages { features: empty.clone(), lines: empty }
+    }
+}
+
+
+/// Brings the image to dark on light and rectifies it, then finds every Table.
+/// Returns the image that the cell positions refer to, along with the Tables.
+/// Spreadsheet headers are checked with the `engine`, if there is one,
+/// and the features and lines are kept in `stages`, if it is given.
+fn find_tables(img: &DynamicImage, params: &DetectionParams, polarity: Polarity,
+               engine: Option<&mut OcrEngine>, stages: Option<&mut Stages>) -> (DynamicImage, Vec<Table>)
+{
+    let (normal, inverted) = binarize::normalize(img, polarity);
+    let Rectified { image, skew, to_original } = rectify(&normal);
+    let tables = detect_tables(&image, params, engine, stages).into_iter()
+        .map(|table| table.with_skew(skew).with_transform(to_original).with_inverted(inverted))
+        .collect();
+    (image, tables)
+}
+
+
+/// Finds the Table in an image that has already been rectified,
+/// along with the features and lines it was found from.
+fn detect_table(img: &DynamicImage, params: &DetectionParams, engine: Option<&mut OcrEngine>) -> (Table, Stages) {
+    let (features, threshold) = detect_features(img, params);
+    let lines = detect_lines(&features, params);
+    let table = table_from_lines(img, &features, &lines, threshold, params, engine);
+    (table, Stages { features, lines })
+}
+
+
+/// Finds each separate Table in an image that has already been rectified.
+fn detect_tables(img: &DynamicImage, params: &DetectionParams, mut engine: Option<&mut OcrEngine>,
+                 mut stages: Option<&mut Stages>) -> Vec<Table> {
+    // Gridlines along the border are kept, since they may belong to the table.
+    let (mut features, threshold) = detect_all_features(img, &params.binarization);
+    let regions = table_regions(&features, params);
+    if regions.is_empty() {
+        remove_boundary_features(&mut features, params.feature_boundary);
+        let lines = detect_lines(&features, params);
+        let table = table_from_lines(img, &features, &lines, threshold, params, engine);
+        if let Some(stages) = stages {
+            *stages = Stages { features, lines };
+        }
+        return vec![table];
+    }
+
+    // Each table is found on its own, as if it had been cropped out,
+    // which also leaves out toolbars and other chrome around it.
+    if let Some(ref mut stages) = stages {
+        let (width, height) = img.dimensions();
+        let black = DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(width, height, Rgba([0, 0, 0, 255])));
+        **stages = Stages { features: black.clone(), lines: black };
+    }
+    let mut img = img.clone();
+    let mut tables: Vec<Table> = regions.iter()
+        .map(|b| {
+            let crop = DynamicImage::ImageRgba8(img.sub_image(b.x, b.y, b.width, b.height).to_image());
+            let engine = engine.as_mut().map(|e| &mut **e as &mut OcrEngine);
+            let (table, crop_stages) = detect_table(&crop, params, engine);
+            if let Some(ref mut stages) = stages {
+                stages.features.copy_from(&crop_stages.features, b.x, b.y);
+                stages.lines.copy_from(&crop_stages.lines, b.x, b.y);
+            }
+            table.with_offset(b.x, b.y)
+        })
+        .collect();
+
+    // Boxes of lines in a toolbar can pass for a grid, but not for a table.
+    let is_table = |t: &Table| t.rows() > 1 && t.cols() > 1;
+    if tables.iter().any(&is_table) {
+        tables.retain(&is_table);
+    }
+    tables
+}
+
+
+/// Where each separate grid of lines is, given every feature in the
+/// image, including those along its border. Empty if there are none.
+fn table_regions(features: &DynamicImage, params: &DetectionParams) -> Vec<Bounds> {
+    regions::find(features, &detect_lines(features, params), params.line_min_length_px)
+}
+
+
+/// Splits the whole image into cells along its lines, or
+/// along the whitespace in its features if there are too few lines.
+/// Spreadsheet headers and empty rows and columns around the data are dropped.
+fn table_from_lines(img: &DynamicImage, features: &DynamicImage, lines: &DynamicImage, threshold: f32,
+                    params: &DetectionParams, engine: Option<&mut OcrEngine>) -> Table {
+    let mut table = detect_cells(lines, params);
+    if table.rows() <= 1 || table.cols() <= 1 {
+        table = projection::detect_cells(features, lines);
+    }
+
+    let (width, height) = features.dimensions();
+    let table = table.with_threshold(threshold)
+        .with_bounds(Bounds { x: 0, y: 0, width, height });
+    let table = chrome::strip_sheet_headers(img, table, engine);
+    chrome::trim_empty(features, table)
+        .with_structure(|cell| confidence::structure(features, lines, cell))
+}
+
+
+/// Crops a single cell out of the image, as the grayscale
+/// image that is handed to the `OcrEngine`.
+/// Light text on a dark fill is inverted, so engines always see dark on light.
+pub fn crop_cell(img: &DynamicImage, cell: &Cell) -> DynamicImage {
+    let mut gray = crop_cell_color(img, cell).grayscale();
+    if is_dark(&gray) {
+        gray.invert();
+    }
+    gray
+}
+
+
+/// Crops the cell in its own colors, for reading its styling.
+pub fn crop_cell_color(img: &DynamicImage, cell: &Cell) -> DynamicImage {
+    DynamicImage::ImageRgba8(ImageBuffer::from_fn(cell.width, cell.height, |x, y| {
+        img.get_pixel(cell.x + x, cell.y + y)
+    }))
 }
 
 
-pub fn get_cells(img: &DynamicImage) -> Vec<Cell> {
-    let features = detect_features(&img);
-    let lines = detect_lines(&features);
-    detect_cells(&lines)
+/// Whether most of the image is darker than mid-gray,
+/// as the background of a cell in a dark fill is.
+fn is_dark(gray: &DynamicImage) -> bool {
+    let luma = gray.to_luma();
+    let dark = luma.pixels().filter(|px| px.data[0] < 128).count();
+    dark * 2 > luma.pixels().count()
 }
 
 
+/// Carries out `config.command`.
 pub fn run(config: Config) -> Result<(), Box<Error>> {
-    let mut img: DynamicImage = image::open(Path::new(&config.filename))?;
+    match config.command {
+        Command::Convert => {
+            let mut engine = config.engine.create()?;
+            run_with_engine(config, &mut *engine)
+        }
+        Command::Cells => run_cells(&config),
+        Command::Words => run_words(&config),
+        Command::Lines => run_lines(&config),
+        // Printing usage is left to the binary.
+        Command::Help => Ok(()),
+    }
+}
+
+
+/// Converts the image as `run` does for `Command::Convert`,
+/// but with a caller-provided engine instead of `config.engine`.
+pub fn run_with_engine(config: Config, engine: &mut OcrEngine) -> Result<(), Box<Error>> {
+    let source = open_image(&config.filename)?;
+    let words = detect_words(&config.filename)?;
+
+    // Cells are found in the normalized and rectified image,
+    // so they are cropped from it too.
+    let mut stages = config.debug_dir.as_ref().map(|_| Stages::empty());
+    let (img, tables) = find_tables(&source, &config.params, config.polarity, Some(&mut *engine), stages.as_mut());
+    if config.verbose {
+        print_tables(&config, &tables);
+    }
+    if let (Some(dir), Some(stages)) = (config.debug_dir.as_ref(), stages.as_ref()) {
+        dump_stages(Path::new(dir), &source, &img, stages, &tables, &words)?;
+    }
+
+    // Failed attempts are told apart by the header row when there is one.
+    let schema = config.schema.clone().unwrap_or_else(Schema::openpowerlifting);
+
+    // Cells to check by hand, with where they are in the original image.
+    let mut review = vec![
+        ["table", "row", "col", "x", "y", "width", "height", "text", "confidence", "structure", "recognition"]
+            .iter().map(|h| h.to_string()).collect::<Vec<_>>()
+    ];
+
+    let mut report = config.report.as_ref().map(|_| Report::new(&config.filename, &source));
+    let mut documents = Vec::new();
+
+    // Recognized text for each table, indexed by [row][col]. Merged cells
+    // put their text in their first row and column, leaving the rest blank.
+    let mut results = Vec::with_capacity(tables.len());
+    for (i, table) in tables.iter().enumerate() {
+        let mut rows = vec![vec![String::new(); table.cols() as usize]; table.rows() as usize];
+        let mut failed = Vec::new();
+        let mut entries = Vec::new();
+        let mut readings = Vec::with_capacity(table.cells().len());
+
+        for cell in table.cells() {
+            let dynimg = crop_cell(&img, cell);
+
+            // Styling is read in the colors of the original image.
+            let mut color = crop_cell_color(&img, cell);
+            if table.is_inverted() {
+                color.invert();
+            }
+            let is_failed = style::analyze(&color).is_failed();
+            if is_failed {
+                failed.push(cell);
+            }
+
+            if let Some(ref dir) = config.crop_dir {
+                let name = if tables.len() > 1 {
+                    format!("{}-{}-{}.png", i + 1, cell.row, cell.col)
+                } else {
+                    format!("{}-{}.png", cell.row, cell.col)
+                };
+                dump(&dynimg, Path::new(dir).join(name))?;
+            }
+
+            let recognition = engine.recognize(&dynimg)?;
+            let confidence = confidence::combine(cell.structure, recognition.confidence);
+            let b = table.original_bounds(cell);
+            if confidence < config.min_confidence {
+                review.push(vec![
+                    (i + 1).to_string(), cell.row.to_string(), cell.col.to_string(),
+                    b.x.to_string(), b.y.to_string(), b.width.to_string(), b.height.to_string(),
+                    recognition.text.clone(), format!("{:.2}", confidence),
+                    format!("{:.2}", cell.structure), format!("{:.2}", recognition.confidence),
+                ]);
+            }
+            readings.push(Reading { bounds: b, recognition: recognition.confidence, confidence, failed: is_failed });
+            if report.is_some() {
+                entries.push(Entry {
+                    cell: cell.clone(),
+                    bounds: b,
+                    crop: color,
+                    text: recognition.text.clone(),
+                    confidence,
+                });
+            }
+            rows[cell.row as usize][cell.col as usize] = recognition.text;
+        }
+        if let Some(ref mut report) = report {
+            report.add_table(table, entries);
+        }
+
+        let attempts = attempt_columns(&schema, &rows);
+        for cell in failed {
+            if attempts[cell.col as usize] {
+                let text = &mut rows[cell.row as usize][cell.col as usize];
+                *text = csv::failed_attempt(text);
+            }
+        }
+        if config.format == OutputFormat::Json {
+            documents.push(table_json(i, table, &rows, &readings, &words));
+        }
+
+        if let Some(ref schema) = config.schema {
+            rows = schema.map_rows(rows);
+        }
+        results.push(rows);
+    }
+
+    if config.verbose {
+        eprintln!("{} cells below confidence {:.2}", review.len() - 1, config.min_confidence);
+    }
+    if let Some(ref path) = config.review {
+        let mut file = File::create(path)?;
+        csv::write_rows(&mut file, &review)?;
+    }
+    if let (Some(path), Some(report)) = (config.report.as_ref(), report.as_ref()) {
+        report.write(&mut File::create(path)?)?;
+    }
+
+    if config.format == OutputFormat::Json {
+        if config.split_tables {
+            let path = config.output.as_ref().ok_or("--split-tables needs --output.")?;
+            for (i, document) in documents.into_iter().enumerate() {
+                let mut file = File::create(numbered(Path::new(path), i + 1))?;
+                image_json(&config, &source, &tables[0], vec![document]).write(&mut file)?;
+            }
+            return Ok(());
+        }
+
+        let document = image_json(&config, &source, &tables[0], documents);
+        match config.output {
+            Some(ref path) => document.write(&mut File::create(path)?)?,
+            None => {
+                let stdout = io::stdout();
+                document.write(&mut stdout.lock())?;
+            }
+        }
+        return Ok(());
+    }
+
+    if config.split_tables {
+        let path = config.output.as_ref().ok_or("--split-tables needs --output.")?;
+        for (i, rows) in results.iter().enumerate() {
+            let mut file = File::create(numbered(Path::new(path), i + 1))?;
+            write_rows(&mut file, config.format, rows)?;
+        }
+        return Ok(());
+    }
+
+    // Several tables share one file, told apart by a leading table number.
+    let rows: Vec<Vec<String>> = if results.len() > 1 {
+        results.iter().enumerate()
+            .flat_map(|(i, rows)| rows.iter().map(move |row| {
+                let mut record = vec![(i + 1).to_string()];
+                record.extend(row.iter().cloned());
+                record
+            }))
+            .collect()
+    } else {
+        results.pop().unwrap_or_default()
+    };
+    write_output(&config, &rows)
+}
+
+
+/// Writes where each cell of each table is in the original image,
+/// and how cleanly its borders were found, without recognizing any text.
+fn run_cells(config: &Config) -> Result<(), Box<Error>> {
+    let source = open_image(&config.filename)?;
+    let mut stages = config.debug_dir.as_ref().map(|_| Stages::empty());
+    let (img, tables) = find_tables(&source, &config.params, config.polarity, None, stages.as_mut());
+    if config.verbose {
+        print_tables(config, &tables);
+    }
+    if let (Some(dir), Some(stages)) = (config.debug_dir.as_ref(), stages.as_ref()) {
+        let words = detect_words(&config.filename)?;
+        dump_stages(Path::new(dir), &source, &img, stages, &tables, &words)?;
+    }
+
+    let mut rows = vec![
+        ["table", "row", "col", "row_span", "col_span", "x", "y", "width", "height", "structure"]
+            .iter().map(|h| h.to_string()).collect::<Vec<_>>()
+    ];
+    for (i, table) in tables.iter().enumerate() {
+        for cell in table.cells() {
+            let b = table.original_bounds(cell);
+            rows.push(vec![
+                (i + 1).to_string(), cell.row.to_string(), cell.col.to_string(),
+                cell.row_span.to_string(), cell.col_span.to_string(),
+                b.x.to_string(), b.y.to_string(), b.width.to_string(), b.height.to_string(),
+                format!("{:.2}", cell.structure),
+            ]);
+        }
+    }
+    write_output(config, &rows)
+}
 
-    let mut pix = Matrix::read(&config.filename, matrix::OpenAs::ToGray).expect("Could not read image");
-    let words = pix.detect_words(Default::default());
 
-    for cell in get_cells(&img) {
-        let subimg = img.sub_image(cell.x, cell.y, cell.width, cell.height);
-        let subimg2 = subimg.to_image();
+/// Writes the word boxes found in the image by stroke width transform.
+fn run_words(config: &Config) -> Result<(), Box<Error>> {
+    // Opened first for an error that says what is wrong with the file.
+    open_image(&config.filename)?;
+    let words = detect_words(&config.filename)?;
+    if config.verbose {
+        eprintln!("{} words", words.len());
+    }
+
+    let mut rows = vec![["x", "y", "width", "height"].iter().map(|h| h.to_string()).collect::<Vec<_>>()];
+    rows.extend(words.iter().map(|w| vec![w.x.to_string(), w.y.to_string(), w.width.to_string(), w.height.to_string()]));
+    write_output(config, &rows)
+}
+
+
+/// Saves the gridlines found in the rectified image to the output path.
+fn run_lines(config: &Config) -> Result<(), Box<Error>> {
+    let source = open_image(&config.filename)?;
+    let (normal, inverted) = binarize::normalize(&source, config.polarity);
+    let img = rectify(&normal).image;
+    let (features, threshold) = detect_features(&img, &config.params);
+    if config.verbose {
+        eprintln!("polarity: {}", if inverted { "inverted" } else { "normal" });
+        eprintln!("binarization: {:?}, mean threshold {:.1}", config.params.binarization, threshold);
+    }
+
+    let path = config.output.as_ref().ok_or("lines needs --output.")?;
+    dump(&detect_lines(&features, &config.params), path)
+}
+
+
+/// Prints how the image was prepared and where its tables are, to stderr.
+fn print_tables(config: &Config, tables: &[Table]) {
+    let first = &tables[0];
+    eprintln!("polarity: {}", if first.is_inverted() { "inverted" } else { "normal" });
+    eprintln!("binarization: {:?}, mean threshold {:.1}", config.params.binarization, first.threshold());
+    eprintln!("skew: {:.2} degrees", first.skew());
+    for (i, table) in tables.iter().enumerate() {
+        let b = table.bounds();
+        eprintln!("table {}: {} rows, {} cols at {}x{}+{}+{}",
+                  i + 1, table.rows(), table.cols(), b.width, b.height, b.x, b.y);
+    }
+}
+
+
+/// Opens the image file, with an error that names it.
+fn open_image(filename: &str) -> Result<DynamicImage, Box<Error>> {
+    match image::open(Path::new(filename)) {
+        Ok(img) => Ok(img),
+        Err(e) => Err(format!("Could not read image {}: {}.", filename, e).into()),
+    }
+}
 
-        let dynimg = DynamicImage::ImageRgba8(subimg2).grayscale();
 
-        dump(&dynimg, &format!("{}-{}.png", cell.row, cell.col));
+/// Finds words in the image file by stroke width transform.
+fn detect_words(filename: &str) -> Result<Vec<Rect>, Box<Error>> {
+    match Matrix::read(filename, matrix::OpenAs::ToGray) {
+        Some(mut pix) => Ok(pix.detect_words(Default::default())),
+        None => Err(format!("Could not read image {} for word detection.", filename).into()),
     }
+}
+
 
+/// Writes rows of text to the output path, or to stdout.
+fn write_output(config: &Config, rows: &[Vec<String>]) -> Result<(), Box<Error>> {
+    match config.output {
+        Some(ref path) => write_rows(&mut File::create(path)?, config.format, rows)?,
+        None => {
+            let stdout = io::stdout();
+            write_rows(&mut stdout.lock(), config.format, rows)?;
+        }
+    }
     Ok(())
 }
+
+
+/// Writes rows of text as CSV, or as TSV if that is the format.
+fn write_rows<W: io::Write>(w: &mut W, format: OutputFormat, rows: &[Vec<String>]) -> io::Result<()> {
+    match format {
+        OutputFormat::Tsv => csv::write_tsv(w, rows),
+        _ => csv::write_rows(w, rows),
+    }
+}
+
+
+/// What was found out about one cell, besides its text.
+struct Reading {
+    /// Where the cell is in the original image.
+    bounds: Bounds,
+    /// The engine's confidence in the text.
+    recognition: f32,
+    /// The confidence in the cell as a whole, from `confidence::combine`.
+    confidence: f32,
+    /// Whether the cell is styled as a failed attempt.
+    failed: bool,
+}
+
+
+/// Describes the image and how it was processed, along with its tables.
+/// The skew and polarity are the same for every table.
+fn image_json(config: &Config, source: &DynamicImage, first: &Table, tables: Vec<Value>) -> Value {
+    let (width, height) = source.dimensions();
+    let params = &config.params;
+    let binarization = match params.binarization {
+        Binarization::Fixed(threshold) => Value::object(vec![
+            ("mode", "fixed".into()), ("threshold", (threshold as u32).into()),
+        ]),
+        Binarization::Otsu => Value::object(vec![("mode", "otsu".into())]),
+        Binarization::Sauvola { window, k } => Value::object(vec![
+            ("mode", "sauvola".into()), ("window", window.into()), ("k", k.into()),
+        ]),
+        Binarization::Contrast { radius, min_contrast } => Value::object(vec![
+            ("mode", "contrast".into()), ("radius", radius.into()), ("min_contrast", (min_contrast as u32).into()),
+        ]),
+    };
+
+    Value::object(vec![
+        ("source", Value::object(vec![
+            ("path", config.filename.as_str().into()),
+            ("width", width.into()),
+            ("height", height.into()),
+        ])),
+        ("skew", first.skew().into()),
+        ("inverted", first.is_inverted().into()),
+        ("binarization", binarization),
+        ("detection", Value::object(vec![
+            ("line_min_length_px", params.line_min_length_px.into()),
+            ("line_featureful_threshold", params.line_featureful_threshold.into()),
+            ("feature_boundary", params.feature_boundary.into()),
+            ("cell_min_height_px", params.cell_min_height_px.into()),
+            ("cell_min_width_px", params.cell_min_width_px.into()),
+        ])),
+        ("min_confidence", config.min_confidence.into()),
+        ("tables", Value::Array(tables)),
+    ])
+}
+
+
+/// Describes one table and each of its cells. Cell rectangles are in the
+/// straightened image that they were cropped from, and `original` is the
+/// box around each in the source image, as are the SWT word rectangles.
+fn table_json(index: usize, table: &Table, rows: &[Vec<String>], readings: &[Reading], words: &[Rect]) -> Value {
+    let bounds_json = |b: &Bounds| Value::object(vec![
+        ("x", b.x.into()), ("y", b.y.into()), ("width", b.width.into()), ("height", b.height.into()),
+    ]);
+
+    let cells = table.cells().iter().zip(readings).map(|(cell, reading)| {
+        // A word belongs to the cell that its middle is in.
+        let b = &reading.bounds;
+        let inside = words.iter()
+            .filter(|w| {
+                let (x, y) = (w.x + w.width / 2, w.y + w.height / 2);
+                x >= b.x as i32 && x < b.right() as i32 && y >= b.y as i32 && y < b.bottom() as i32
+            })
+            .map(|w| Value::object(vec![
+                ("x", Value::Number(w.x as f64)), ("y", Value::Number(w.y as f64)),
+                ("width", Value::Number(w.width as f64)), ("height", Value::Number(w.height as f64)),
+            ]))
+            .collect();
+
+        Value::object(vec![
+            ("row", cell.row.into()),
+            ("col", cell.col.into()),
+            ("row_span", cell.row_span.into()),
+            ("col_span", cell.col_span.into()),
+            ("x", cell.x.into()),
+            ("y", cell.y.into()),
+            ("width", cell.width.into()),
+            ("height", cell.height.into()),
+            ("original", bounds_json(b)),
+            ("text", rows[cell.row as usize][cell.col as usize].as_str().into()),
+            ("confidence", reading.confidence.into()),
+            ("structure", cell.structure.into()),
+            ("recognition", reading.recognition.into()),
+            ("failed", reading.failed.into()),
+            ("words", Value::Array(inside)),
+        ])
+    }).collect();
+
+    Value::object(vec![
+        ("table", (index as u32 + 1).into()),
+        ("rows", table.rows().into()),
+        ("cols", table.cols().into()),
+        ("bounds", bounds_json(&table.bounds())),
+        ("threshold", table.threshold().into()),
+        ("cells", Value::Array(cells)),
+    ])
+}
+
+
+/// Which columns hold single attempts, going by the header row.
+/// Without a header row, none are known to, so none are changed.
+fn attempt_columns(schema: &Schema, rows: &[Vec<String>]) -> Vec<bool> {
+    let cols = rows.first().map_or(0, |row| row.len());
+    match schema.header_row(rows) {
+        Some(header) => rows[header].iter()
+            .map(|h| schema.canonical(h).is_some_and(schema::is_attempt))
+            .collect(),
+        None => vec![false; cols],
+    }
+}
+
+
+/// Numbers a path for one of several tables, as `out.csv` becomes `out-2.csv`.
+fn numbered(path: &Path, n: usize) -> PathBuf {
+    let stem = path.file_stem().map_or(String::new(), |s| s.to_string_lossy().into_owned());
+    let name = match path.extension() {
+        Some(ext) => format!("{}-{}.{}", stem, n, ext.to_string_lossy()),
+        None => format!("{}-{}", stem, n),
+    };
+    path.with_file_name(name)
+}
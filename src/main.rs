@@ -1,17 +1,64 @@
 extern crate img2csv;
 
-use img2csv::Config;
+use img2csv::{Command, Config};
 use std::env;
 use std::process;
 
+const USAGE: &str = "\
+Usage: img2csv [COMMAND] IMAGE [OPTIONS]
+
+Commands:
+  convert                 Recognize every cell and write the tables (the default)
+  cells                   Write where each cell is, without recognizing text
+  words                   Write the word boxes found by stroke width transform
+  lines                   Save the gridlines that cells are found along, as a PNG
+  help                    Print this message
+
+Output:
+  -o, --output PATH       Write to PATH instead of stdout
+  -f, --format FMT        csv, tsv or json (json only for convert; default csv)
+  --split-tables          Write each table to its own numbered file (needs -o)
+  --map-headers           Rename the header row to OpenPowerlifting columns
+  --synonyms FILE         Add header synonyms, one `Canonical = a, b` per line
+  --review PATH           List cells below --min-confidence in a CSV file
+  --min-confidence N      Confidence from 0 to 1 below which cells are listed (0.8)
+  --report PATH           Write an HTML page for proofreading
+
+Detection:
+  --engine NAME           OCR engine: builtin, null or mock, or tesseract if built in
+  --binarize MODE         contrast, otsu, sauvola or fixed:N (default contrast)
+  --polarity P            auto, normal or inverted (default auto)
+  --line-min-length N     Shortest stretch of ink that is a gridline (50)
+  --line-threshold F      Fraction of that stretch that must be ink (0.95)
+  --feature-boundary N    Margin of the image that is ignored (8)
+  --cell-min-height N     Shortest cell (10)
+  --cell-min-width N      Narrowest cell (8)
+  --params FILE           Read detection parameters, one `name = value` per line
+
+Debugging:
+  --crops DIR             Save each cell as DIR/{row}-{col}.png
+  --debug-dir DIR         Save the rectified image, features, lines and an overlay
+  -v, --verbose           Print diagnostics to stderr
+  -h, --help              Print this message
+
+Mistakes on the command line exit with status 2, and failures while
+converting with status 1.
+";
+
 fn main() {
     let config = Config::new(env::args()).unwrap_or_else(|err| {
-        eprintln!("Problem parsing arguments: {}", err);
-        process::exit(1);
+        eprintln!("img2csv: {}", err);
+        eprintln!("Run `img2csv --help` for usage.");
+        process::exit(2);
     });
 
+    if config.command == Command::Help {
+        print!("{}", USAGE);
+        return;
+    }
+
     if let Err(e) = img2csv::run(config) {
-        eprintln!("Application error: {}", e);
+        eprintln!("img2csv: {}", e);
         process::exit(1);
     }
 }
@@ -0,0 +1,157 @@
+//! Recognition of the text inside a single cell.
+//!
+//! Cell detection and CSV output never look inside a cell; they hand each
+//! crop to an `OcrEngine` and use whatever text it returns.
+
+use image::{DynamicImage, GenericImage};
+
+use builtin::BuiltinEngine;
+
+use std::collections::VecDeque;
+use std::error::Error;
+
+/// The text recognized within one cell.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recognition {
+    pub text: String,
+    /// How sure the engine is of `text`, from 0.0 (guess) to 1.0 (certain).
+    pub confidence: f32,
+}
+
+impl Recognition {
+    pub fn new<S: Into<String>>(text: S, confidence: f32) -> Recognition {
+        Recognition { text: text.into(), confidence }
+    }
+
+    /// A recognition for a cell that holds no text. There is nothing
+    /// to misread, so its confidence is 1.0.
+    pub fn empty() -> Recognition {
+        Recognition::new("", 1.0)
+    }
+}
+
+/// Anything that can turn a cell crop into text.
+///
+/// Every engine scores its readings the same way, so that the cells
+/// listed for review do not depend on the engine: a cell that the engine
+/// finds blank is `Recognition::empty()`, and ink that it cannot make out
+/// is its best guess, which may be empty, with a confidence near 0.0.
+pub trait OcrEngine {
+    /// Recognizes the text in `cell`, which is the grayscale crop of
+    /// a single `Cell` from the source image.
+    fn recognize(&mut self, cell: &DynamicImage) -> Result<Recognition, Box<Error>>;
+
+    /// Whether the text comes from the ink in the cell. Only engines that
+    /// read ink are asked to check what cell detection found, such as
+    /// the labels of spreadsheet headers, before the cells are recognized.
+    fn reads_ink(&self) -> bool {
+        true
+    }
+}
+
+/// Selects which `OcrEngine` `run` should use.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EngineKind {
+    /// Recognizes nothing: every cell is empty.
+    Null,
+    /// The deterministic `MockEngine`.
+    Mock,
+    /// The pure-Rust `BuiltinEngine`.
+    Builtin,
+    /// libtesseract, if built with the `tesseract` feature.
+    Tesseract,
+}
+
+impl EngineKind {
+    pub fn from_name(name: &str) -> Option<EngineKind> {
+        match name {
+            "null" => Some(EngineKind::Null),
+            "mock" => Some(EngineKind::Mock),
+            "builtin" => Some(EngineKind::Builtin),
+            "tesseract" => Some(EngineKind::Tesseract),
+            _ => None,
+        }
+    }
+
+    /// Instantiates the selected engine.
+    pub fn create(&self) -> Result<Box<OcrEngine>, Box<Error>> {
+        match *self {
+            EngineKind::Null => Ok(Box::new(NullEngine)),
+            EngineKind::Mock => Ok(Box::new(MockEngine::new())),
+            EngineKind::Builtin => Ok(Box::new(BuiltinEngine::new())),
+            EngineKind::Tesseract => create_tesseract(),
+        }
+    }
+}
+
+#[cfg(feature = "tesseract")]
+fn create_tesseract() -> Result<Box<OcrEngine>, Box<Error>> {
+    Ok(Box::new(::tesseract::TesseractEngine::new("eng")?))
+}
+
+#[cfg(not(feature = "tesseract"))]
+fn create_tesseract() -> Result<Box<OcrEngine>, Box<Error>> {
+    Err("img2csv was built without the `tesseract` feature.".into())
+}
+
+/// Uses Tesseract when it is available, and the pure-Rust fallback otherwise.
+impl Default for EngineKind {
+    #[cfg(feature = "tesseract")]
+    fn default() -> EngineKind {
+        EngineKind::Tesseract
+    }
+
+    #[cfg(not(feature = "tesseract"))]
+    fn default() -> EngineKind {
+        EngineKind::Builtin
+    }
+}
+
+/// An engine that never recognizes anything: every cell reads as blank.
+pub struct NullEngine;
+
+impl OcrEngine for NullEngine {
+    fn recognize(&mut self, _cell: &DynamicImage) -> Result<Recognition, Box<Error>> {
+        Ok(Recognition::empty())
+    }
+
+    fn reads_ink(&self) -> bool {
+        false
+    }
+}
+
+/// A deterministic engine for tests.
+///
+/// Queued responses are returned in order, one per call. Once the queue is
+/// empty, each cell is described by its dimensions, as in `"37x12"`.
+/// It does not read ink, so only the cells of the output use up the queue.
+#[derive(Default)]
+pub struct MockEngine {
+    responses: VecDeque<Recognition>,
+}
+
+impl MockEngine {
+    pub fn new() -> MockEngine {
+        MockEngine { responses: VecDeque::new() }
+    }
+
+    /// Creates an engine that answers with `responses`, in order.
+    pub fn with_responses(responses: Vec<Recognition>) -> MockEngine {
+        MockEngine { responses: responses.into_iter().collect() }
+    }
+}
+
+impl OcrEngine for MockEngine {
+    fn recognize(&mut self, cell: &DynamicImage) -> Result<Recognition, Box<Error>> {
+        if let Some(response) = self.responses.pop_front() {
+            return Ok(response);
+        }
+
+        let (width, height) = cell.dimensions();
+        Ok(Recognition::new(format!("{}x{}", width, height), 1.0))
+    }
+
+    fn reads_ink(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,104 @@
+//! Drawings of what was found in an image, for debugging.
+//!
+//! Cells are outlined where they are in the original image, so a skewed
+//! or warped table shows its cells as tilted quadrilaterals, and each is
+//! labelled with its row and column in the built-in `font`, scaled up
+//! with the image as far as the cells have room for. Word boxes
+//! from the stroke width transform are outlined in another color, which
+//! shows text that straddles a border or was missed altogether.
+
+use image::{DynamicImage, Rgba, RgbaImage};
+
+use font;
+use table::{Bounds, Table};
+
+/// The color of cell outlines and their labels.
+pub const CELL_COLOR: Rgba<u8> = Rgba { data: [255, 0, 0, 255] };
+
+/// The color of word boxes.
+pub const WORD_COLOR: Rgba<u8> = Rgba { data: [0, 0, 255, 255] };
+
+/// Pixels between a cell's top-left corner and its label, at scale 1.
+const LABEL_INSET_PX: u32 = 2;
+
+/// Labels are drawn one pixel per font pixel on images up to this size
+/// along their longer side, and a pixel larger for each multiple of it,
+/// as long as they fit in the shortest cell of their table.
+const LABEL_SCALE_PX: u32 = 800;
+
+
+/// The source image with every cell of the tables outlined and labelled
+/// `row,col`, and with the given word boxes outlined.
+pub fn draw(source: &DynamicImage, tables: &[Table], words: &[Bounds]) -> DynamicImage {
+    let mut img = source.to_rgba();
+    let (width, height) = img.dimensions();
+    let image_scale = u32::max(width, height) / LABEL_SCALE_PX;
+
+    for word in words {
+        let (x0, y0) = (word.x as f32, word.y as f32);
+        let (x1, y1) = (word.right() as f32, word.bottom() as f32);
+        draw_polygon(&mut img, &[(x0, y0), (x1, y0), (x1, y1), (x0, y1)], WORD_COLOR);
+    }
+
+    for table in tables {
+        let shortest = table.cells().iter().map(|c| c.height).min().unwrap_or(0);
+        let scale = image_scale.min(shortest / (font::GLYPH_HEIGHT + 2 * LABEL_INSET_PX)).max(1);
+        let inset = LABEL_INSET_PX * scale;
+
+        for cell in table.cells() {
+            let (x0, y0) = (cell.x as f32, cell.y as f32);
+            let (x1, y1) = ((cell.x + cell.width) as f32, (cell.y + cell.height) as f32);
+            let corners = [table.to_original(x0, y0), table.to_original(x1, y0),
+                           table.to_original(x1, y1), table.to_original(x0, y1)];
+            draw_polygon(&mut img, &corners, CELL_COLOR);
+
+            let (x, y) = corners[0];
+            let label = format!("{},{}", cell.row, cell.col);
+            draw_label(&mut img, &label, x.max(0.0) as u32 + inset, y.max(0.0) as u32 + inset, scale);
+        }
+    }
+
+    DynamicImage::ImageRgba8(img)
+}
+
+
+/// Draws the closed outline through the points, one pixel wide.
+fn draw_polygon(img: &mut RgbaImage, points: &[(f32, f32)], color: Rgba<u8>) {
+    for (i, &(x0, y0)) in points.iter().enumerate() {
+        let (x1, y1) = points[(i + 1) % points.len()];
+        let steps = f32::max((x1 - x0).abs(), (y1 - y0).abs()).ceil().max(1.0) as u32;
+        for k in 0 ..= steps {
+            let t = k as f32 / steps as f32;
+            put(img, (x0 + t * (x1 - x0)).round(), (y0 + t * (y1 - y0)).round(), color);
+        }
+    }
+}
+
+
+/// Draws text with each font pixel `scale` pixels square, and its top-left corner at (x, y).
+fn draw_label(img: &mut RgbaImage, text: &str, x: u32, y: u32, scale: u32) {
+    for (i, ch) in text.chars().enumerate() {
+        let glyph = match font::glyph(ch) {
+            Some(glyph) => glyph,
+            None => continue,
+        };
+        let left = x + (font::GLYPH_WIDTH + 1) * scale * i as u32;
+
+        for gy in 0 .. font::GLYPH_HEIGHT * scale {
+            for gx in 0 .. font::GLYPH_WIDTH * scale {
+                if glyph.is_set(gx / scale, gy / scale) {
+                    put(img, (left + gx) as f32, (y + gy) as f32, CELL_COLOR);
+                }
+            }
+        }
+    }
+}
+
+
+/// Sets the pixel, if it is inside the image.
+fn put(img: &mut RgbaImage, x: f32, y: f32, color: Rgba<u8>) {
+    let (width, height) = img.dimensions();
+    if x >= 0.0 && y >= 0.0 && (x as u32) < width && (y as u32) < height {
+        img.put_pixel(x as u32, y as u32, color);
+    }
+}
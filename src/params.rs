@@ -0,0 +1,114 @@
+//! Tuning for how tables are found.
+//!
+//! The defaults suit screenshots of spreadsheets at their usual size.
+//! Federations whose sheets have unusual dimensions, such as very small
+//! cells or short gridlines, can be read by changing them at runtime,
+//! from code, the command line, or a file of `name = value` lines.
+
+use binarize::Binarization;
+
+/// The minimum length of a stretch of pixels that can make up a line.
+const LINE_MIN_LENGTH_PX: u32 = 50;
+
+/// The fraction of pixels in a span of LINE_MIN_LENGTH_PX pixels
+/// that must be featureful for all to be considered a solid line.
+const LINE_FEATUREFUL_THRESHOLD: f32 = 0.95;
+
+/// The boundary along the image border that should be ignored for
+/// feature detection.
+const FEATURE_BOUNDARY: u32 = 8;
+
+/// The minimum height of a cell.
+const CELL_MIN_HEIGHT_PX: u32 = 10;
+
+/// The minimum width of a cell.
+const CELL_MIN_WIDTH_PX: u32 = 8;
+
+/// The smallest that lines and cells can be set to. A cell needs room
+/// for the line that ends it, and a line is more than a speck.
+const MIN_PX: u32 = 2;
+
+
+/// The parameters of feature, line and cell detection.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DetectionParams {
+    /// How ink is told apart from background, including its darkness threshold.
+    pub binarization: Binarization,
+    /// The minimum length of a stretch of pixels that can make up a line.
+    pub line_min_length_px: u32,
+    /// The fraction of pixels in a span of `line_min_length_px` pixels
+    /// that must be featureful for all to be considered a solid line.
+    pub line_featureful_threshold: f32,
+    /// The boundary along the image border that is ignored for feature detection.
+    pub feature_boundary: u32,
+    /// The minimum height of a cell.
+    pub cell_min_height_px: u32,
+    /// The minimum width of a cell.
+    pub cell_min_width_px: u32,
+}
+
+impl Default for DetectionParams {
+    fn default() -> DetectionParams {
+        DetectionParams {
+            binarization: Binarization::default(),
+            line_min_length_px: LINE_MIN_LENGTH_PX,
+            line_featureful_threshold: LINE_FEATUREFUL_THRESHOLD,
+            feature_boundary: FEATURE_BOUNDARY,
+            cell_min_height_px: CELL_MIN_HEIGHT_PX,
+            cell_min_width_px: CELL_MIN_WIDTH_PX,
+        }
+    }
+}
+
+impl DetectionParams {
+    /// Sets the parameter with the given field name from text, as
+    /// `set("cell_min_width_px", "6")` does. Binarization takes the
+    /// names that `Binarization::from_name` parses.
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        let pixels = || match value.trim().parse::<u32>() {
+            Ok(n) if n >= MIN_PX => Ok(n),
+            _ => Err(format!("{} needs a whole number of pixels, at least {}, not {}.", name, MIN_PX, value)),
+        };
+
+        match name {
+            "binarization" => {
+                self.binarization = Binarization::from_name(value.trim())
+                    .ok_or_else(|| format!("Unknown binarization {}; use contrast, otsu, sauvola or fixed:N.", value))?;
+            }
+            "line_min_length_px" => self.line_min_length_px = pixels()?,
+            "line_featureful_threshold" => {
+                self.line_featureful_threshold = match value.trim().parse::<f32>() {
+                    Ok(n) if n > 0.0 && n <= 1.0 => n,
+                    _ => return Err(format!("{} needs a fraction above 0 and at most 1, not {}.", name, value)),
+                };
+            }
+            "feature_boundary" => {
+                self.feature_boundary = value.trim().parse()
+                    .map_err(|_| format!("{} needs a whole number of pixels, not {}.", name, value))?;
+            }
+            "cell_min_height_px" => self.cell_min_height_px = pixels()?,
+            "cell_min_width_px" => self.cell_min_width_px = pixels()?,
+            _ => return Err(format!("Unknown detection parameter {}.", name)),
+        }
+        Ok(())
+    }
+
+    /// Sets parameters from text with one per line, as in
+    /// `cell_min_height_px = 6`. Blank lines and lines
+    /// starting with `#` are skipped.
+    pub fn set_from_str(&mut self, text: &str) -> Result<(), String> {
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.splitn(2, '=');
+            let name = parts.next().unwrap_or("").trim();
+            match parts.next() {
+                Some(value) if !name.is_empty() => self.set(name, value)?,
+                _ => return Err(format!("Parameter lines must look like `name = value`, not `{}`.", line)),
+            }
+        }
+        Ok(())
+    }
+}
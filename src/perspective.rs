@@ -0,0 +1,398 @@
+//! Correction of perspective in photos of printed or on-screen tables.
+//!
+//! A phone photo of a sheet turns the table's rectangular outline into a
+//! trapezoid, so its gridlines are neither parallel nor axis-aligned. The
+//! outline is found as the largest connected blob of ink, and the image is
+//! warped so that its four corners become a rectangle again. Other
+//! tables in the photo lie on the same page, so they are flattened by
+//! the same warp.
+
+use std::collections::VecDeque;
+
+use image::{DynamicImage, GenericImage, GrayImage, ImageBuffer, RgbaImage};
+
+use binarize;
+use geometry;
+
+/// Outlines whose corners are all this close to an axis-aligned
+/// rectangle are left alone.
+const MAX_SQUARENESS_ERROR_PX: f32 = 2.0;
+
+/// Sides whose outermost ink strays further than this from a straight
+/// line, on average, are not part of a drawn outline.
+const MAX_EDGE_RESIDUAL_PX: f32 = 1.5;
+
+/// How many times the worst quarter of the points along a side
+/// are dropped before the final fit.
+const EDGE_REFITS: usize = 3;
+
+/// The outline must span at least this fraction of the image's width and height.
+const MIN_OUTLINE_FRACTION: f32 = 0.25;
+
+/// Another blob of ink at least this fraction of the outline's width and
+/// height, lying outside it, is taken for a second table.
+const MIN_OTHER_TABLE_FRACTION: f32 = 0.1;
+
+/// When the whole photo is warped, it may grow by at most this factor
+/// in each direction. Anything more is too far from the table to trust.
+const MAX_PHOTO_GROWTH: f32 = 2.0;
+
+/// Whitespace kept around the table after warping, so that its outer
+/// gridlines survive resampling. Anything further out is cropped off.
+const MARGIN_PX: u32 = 2;
+
+
+/// A projective transform of the plane, as a 3x3 matrix in row-major order.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Homography {
+    m: [f64; 9],
+}
+
+impl Homography {
+    /// The transform that leaves every point where it is.
+    pub fn identity() -> Homography {
+        Homography { m: [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0] }
+    }
+
+    /// The transform that moves every point by (dx, dy).
+    pub fn translation(dx: f32, dy: f32) -> Homography {
+        Homography { m: [1.0, 0.0, dx as f64, 0.0, 1.0, dy as f64, 0.0, 0.0, 1.0] }
+    }
+
+    /// The transform that rotates points counterclockwise by `radians`
+    /// about (cx, cy), as `geometry::rotate_point` does.
+    pub fn rotation(cx: f32, cy: f32, radians: f32) -> Homography {
+        let (sin, cos) = (radians as f64).sin_cos();
+        let (cx, cy) = (cx as f64, cy as f64);
+        Homography { m: [
+            cos, sin, cx - cx * cos - cy * sin,
+            -sin, cos, cy + cx * sin - cy * cos,
+            0.0, 0.0, 1.0,
+        ] }
+    }
+
+    /// The transform that takes each of the `from` points to the
+    /// corresponding `to` point. None if three of the points are collinear.
+    pub fn from_points(from: &[(f32, f32); 4], to: &[(f32, f32); 4]) -> Option<Homography> {
+        // Each correspondence gives two equations in the eight unknowns,
+        // with the bottom-right entry fixed at one.
+        let mut a = [[0.0f64; 9]; 8];
+        for i in 0 .. 4 {
+            let (x, y) = (from[i].0 as f64, from[i].1 as f64);
+            let (u, v) = (to[i].0 as f64, to[i].1 as f64);
+            a[2 * i] = [x, y, 1.0, 0.0, 0.0, 0.0, -u * x, -u * y, u];
+            a[2 * i + 1] = [0.0, 0.0, 0.0, x, y, 1.0, -v * x, -v * y, v];
+        }
+
+        let h = solve(&mut a)?;
+        Some(Homography { m: [h[0], h[1], h[2], h[3], h[4], h[5], h[6], h[7], 1.0] })
+    }
+
+    /// Maps a point through the transform.
+    pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        let m = &self.m;
+        let (x, y) = (x as f64, y as f64);
+        let w = m[6] * x + m[7] * y + m[8];
+        (((m[0] * x + m[1] * y + m[2]) / w) as f32, ((m[3] * x + m[4] * y + m[5]) / w) as f32)
+    }
+
+    /// The transform that applies `self` and then `other`.
+    pub fn then(&self, other: &Homography) -> Homography {
+        let (a, b) = (&other.m, &self.m);
+        let mut m = [0.0; 9];
+        for r in 0 .. 3 {
+            for c in 0 .. 3 {
+                m[3 * r + c] = (0 .. 3).map(|k| a[3 * r + k] * b[3 * k + c]).sum();
+            }
+        }
+        Homography { m }
+    }
+}
+
+
+/// Solves an 8x8 linear system, given as an augmented matrix,
+/// by Gaussian elimination with partial pivoting.
+fn solve(a: &mut [[f64; 9]; 8]) -> Option<[f64; 8]> {
+    for col in 0 .. 8 {
+        let pivot = (col .. 8).max_by(|&i, &j| a[i][col].abs().partial_cmp(&a[j][col].abs()).unwrap())?;
+        if a[pivot][col].abs() < 1e-9 {
+            return None;
+        }
+        a.swap(col, pivot);
+
+        for row in 0 .. 8 {
+            if row != col {
+                let (pivot_row, factor) = (a[col], a[row][col] / a[col][col]);
+                for (x, p) in a[row][col ..].iter_mut().zip(&pivot_row[col ..]) {
+                    *x -= factor * p;
+                }
+            }
+        }
+    }
+
+    let mut x = [0.0; 8];
+    for i in 0 .. 8 {
+        x[i] = a[i][8] / a[i][i];
+    }
+    Some(x)
+}
+
+
+/// Finds the corners of the table's outline: top-left, top-right,
+/// bottom-right and bottom-left. None if the largest blob of ink is too
+/// small or its sides are not straight, as when the table has no border.
+pub fn find_outline(img: &DynamicImage) -> Option<[(f32, f32); 4]> {
+    find_outlines(img).map(|(corners, _)| corners)
+}
+
+
+/// Like `find_outline`, but also tells whether there are other tables
+/// outside the outline.
+fn find_outlines(img: &DynamicImage) -> Option<([(f32, f32); 4], bool)> {
+    let gray = img.to_luma();
+    let (width, height) = gray.dimensions();
+    let threshold = binarize::otsu_threshold(&binarize::histogram(&gray));
+
+    let (blob, boxes) = blobs(&gray, threshold);
+    if blob.is_empty() {
+        return None;
+    }
+
+    let min_x = blob.iter().map(|p| p.0).min().unwrap();
+    let max_x = blob.iter().map(|p| p.0).max().unwrap();
+    let min_y = blob.iter().map(|p| p.1).min().unwrap();
+    let max_y = blob.iter().map(|p| p.1).max().unwrap();
+    if ((max_x - min_x) as f32) < width as f32 * MIN_OUTLINE_FRACTION
+        || ((max_y - min_y) as f32) < height as f32 * MIN_OUTLINE_FRACTION
+    {
+        return None;
+    }
+
+    let is_other_table = |&(left, top, right, bottom): &BlobBox| {
+        let outside = right < min_x || left > max_x || bottom < min_y || top > max_y;
+        outside
+            && (right - left) as f32 >= (max_x - min_x) as f32 * MIN_OTHER_TABLE_FRACTION
+            && (bottom - top) as f32 >= (max_y - min_y) as f32 * MIN_OTHER_TABLE_FRACTION
+    };
+    let others = boxes.iter().any(is_other_table);
+
+    // The outermost ink along each side traces the outline. Only the middle
+    // half of each side is fitted, since corners may be clipped or rounded.
+    let mut top: Vec<Option<u32>> = vec![None; width as usize];
+    let mut bottom: Vec<Option<u32>> = vec![None; width as usize];
+    let mut left: Vec<Option<u32>> = vec![None; height as usize];
+    let mut right: Vec<Option<u32>> = vec![None; height as usize];
+    for &(x, y) in &blob {
+        let (xi, yi) = (x as usize, y as usize);
+        top[xi] = Some(top[xi].map_or(y, |t| u32::min(t, y)));
+        bottom[xi] = Some(bottom[xi].map_or(y, |b| u32::max(b, y)));
+        left[yi] = Some(left[yi].map_or(x, |l| u32::min(l, x)));
+        right[yi] = Some(right[yi].map_or(x, |r| u32::max(r, x)));
+    }
+
+    let (top, bottom) = (fit_edge(&top)?, fit_edge(&bottom)?);
+    let (left, right) = (fit_edge(&left)?, fit_edge(&right)?);
+
+    let corners = [
+        intersect(top, left),
+        intersect(top, right),
+        intersect(bottom, right),
+        intersect(bottom, left),
+    ];
+    Some((corners, others))
+}
+
+
+/// A straight edge, as the offset and slope of `v = a + b * t`,
+/// where t runs along the edge and v across it.
+type Edge = (f32, f32);
+
+
+/// Fits an edge to the outermost ink at each position along it, using
+/// only the middle half of the positions. Gaps in a faint outline let
+/// ink further in show through, so the worst-fitting points are dropped
+/// and the edge refitted a few times. None if what remains does not lie
+/// along a straight line.
+fn fit_edge(outermost: &[Option<u32>]) -> Option<Edge> {
+    let points: Vec<(f32, f32)> = outermost.iter().enumerate()
+        .filter_map(|(t, v)| v.map(|v| (t as f32, v as f32)))
+        .collect();
+    if points.len() < 8 {
+        return None;
+    }
+
+    let mut inliers = points[points.len() / 4 .. points.len() * 3 / 4].to_vec();
+    let mut edge = least_squares(&inliers)?;
+    for _ in 0 .. EDGE_REFITS {
+        let distance = |p: &(f32, f32)| (p.1 - edge.0 - edge.1 * p.0).abs();
+        inliers.sort_by(|a, b| distance(a).partial_cmp(&distance(b)).unwrap());
+        let keep = inliers.len() * 3 / 4;
+        inliers.truncate(keep);
+        edge = least_squares(&inliers)?;
+    }
+
+    let n = inliers.len() as f32;
+    let residual = (inliers.iter().map(|p| (p.1 - edge.0 - edge.1 * p.0).powi(2)).sum::<f32>() / n).sqrt();
+    if residual > MAX_EDGE_RESIDUAL_PX {
+        return None;
+    }
+
+    Some(edge)
+}
+
+
+/// The least-squares fit of `v = a + b * t` to the points.
+fn least_squares(points: &[(f32, f32)]) -> Option<Edge> {
+    let n = points.len() as f32;
+    let mean_t = points.iter().map(|p| p.0).sum::<f32>() / n;
+    let mean_v = points.iter().map(|p| p.1).sum::<f32>() / n;
+    let covariance: f32 = points.iter().map(|p| (p.0 - mean_t) * (p.1 - mean_v)).sum();
+    let variance: f32 = points.iter().map(|p| (p.0 - mean_t).powi(2)).sum();
+    if variance == 0.0 {
+        return None;
+    }
+
+    let slope = covariance / variance;
+    Some((mean_v - slope * mean_t, slope))
+}
+
+
+/// Where a horizontal edge, with t along x, meets a vertical edge, with t along y.
+fn intersect(horizontal: Edge, vertical: Edge) -> (f32, f32) {
+    let (a1, b1) = horizontal;
+    let (a2, b2) = vertical;
+    let x = (a2 + b2 * a1) / (1.0 - b2 * b1);
+    (x, a1 + b1 * x)
+}
+
+
+/// The bounding box of a blob: left, top, right and bottom, inclusive.
+type BlobBox = (u32, u32, u32, u32);
+
+
+/// The pixels of the largest 8-connected blob of ink,
+/// and the bounding box of every blob.
+fn blobs(gray: &GrayImage, threshold: u8) -> (Vec<(u32, u32)>, Vec<BlobBox>) {
+    let (width, height) = gray.dimensions();
+    let mut seen = vec![false; (width * height) as usize];
+    let is_ink = |x: u32, y: u32| gray.get_pixel(x, y).data[0] <= threshold;
+
+    let mut largest = Vec::new();
+    let mut boxes = Vec::new();
+    let mut queue = VecDeque::new();
+
+    for start_y in 0 .. height {
+        for start_x in 0 .. width {
+            let start = (start_y * width + start_x) as usize;
+            if seen[start] || !is_ink(start_x, start_y) {
+                continue;
+            }
+
+            seen[start] = true;
+            queue.push_back((start_x, start_y));
+            let mut blob = Vec::new();
+
+            while let Some((x, y)) = queue.pop_front() {
+                blob.push((x, y));
+                for dy in -1i64 .. 2 {
+                    for dx in -1i64 .. 2 {
+                        let (nx, ny) = (x as i64 + dx, y as i64 + dy);
+                        if nx < 0 || ny < 0 || nx >= width as i64 || ny >= height as i64 {
+                            continue;
+                        }
+                        let i = (ny as u32 * width + nx as u32) as usize;
+                        if !seen[i] && is_ink(nx as u32, ny as u32) {
+                            seen[i] = true;
+                            queue.push_back((nx as u32, ny as u32));
+                        }
+                    }
+                }
+            }
+
+            boxes.push((blob.iter().map(|p| p.0).min().unwrap(), blob.iter().map(|p| p.1).min().unwrap(),
+                        blob.iter().map(|p| p.0).max().unwrap(), blob.iter().map(|p| p.1).max().unwrap()));
+            if blob.len() > largest.len() {
+                largest = blob;
+            }
+        }
+    }
+
+    (largest, boxes)
+}
+
+
+/// Whether the outline is already an axis-aligned rectangle.
+fn is_square(corners: &[(f32, f32); 4]) -> bool {
+    let [tl, tr, br, bl] = *corners;
+    (tl.0 - bl.0).abs() <= MAX_SQUARENESS_ERROR_PX
+        && (tr.0 - br.0).abs() <= MAX_SQUARENESS_ERROR_PX
+        && (tl.1 - tr.1).abs() <= MAX_SQUARENESS_ERROR_PX
+        && (bl.1 - br.1).abs() <= MAX_SQUARENESS_ERROR_PX
+}
+
+
+fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
+
+
+/// Resamples the image into a `width` by `height` image, where each output
+/// pixel comes from where `to_source` maps it. Uncovered areas take the
+/// background color.
+pub fn warp(img: &DynamicImage, to_source: &Homography, width: u32, height: u32) -> DynamicImage {
+    let background = geometry::background_color(img);
+    let out: RgbaImage = ImageBuffer::from_fn(width, height, |x, y| {
+        let (sx, sy) = to_source.apply(x as f32, y as f32);
+        geometry::sample_bilinear(img, sx, sy, background)
+    });
+    DynamicImage::ImageRgba8(out)
+}
+
+
+/// Finds the table's outline and warps it to a rectangle.
+///
+/// Returns the corrected image and the transform that maps its
+/// coordinates back to the original, or None if the outline was
+/// not found or is already rectangular. The image is cropped to the
+/// outline, unless there are other tables outside it; then the whole
+/// photo is warped instead.
+pub fn correct(img: &DynamicImage) -> Option<(DynamicImage, Homography)> {
+    let (corners, others) = find_outlines(img)?;
+    if is_square(&corners) {
+        return None;
+    }
+
+    let [tl, tr, br, bl] = corners;
+    let width = f32::max(distance(tl, tr), distance(bl, br)).round() as u32;
+    let height = f32::max(distance(tl, bl), distance(tr, br)).round() as u32;
+
+    let (left, top) = (MARGIN_PX as f32, MARGIN_PX as f32);
+    let (right, bottom) = (left + width as f32, top + height as f32);
+    let rectangle = [(left, top), (right, top), (right, bottom), (left, bottom)];
+
+    let to_source = Homography::from_points(&rectangle, &corners)?;
+    if !others {
+        let corrected = warp(img, &to_source, width + 2 * MARGIN_PX + 1, height + 2 * MARGIN_PX + 1);
+        return Some((corrected, to_source));
+    }
+
+    // Where the corners of the photo end up once it is flattened.
+    let to_rectangle = Homography::from_points(&corners, &rectangle)?;
+    let (img_width, img_height) = img.dimensions();
+    let (right, bottom) = ((img_width - 1) as f32, (img_height - 1) as f32);
+    let photo: Vec<(f32, f32)> = [(0.0, 0.0), (right, 0.0), (right, bottom), (0.0, bottom)].iter()
+        .map(|&(x, y)| to_rectangle.apply(x, y))
+        .collect();
+    let min_x = photo.iter().map(|p| p.0).fold(f32::INFINITY, f32::min).floor();
+    let max_x = photo.iter().map(|p| p.0).fold(f32::NEG_INFINITY, f32::max).ceil();
+    let min_y = photo.iter().map(|p| p.1).fold(f32::INFINITY, f32::min).floor();
+    let max_y = photo.iter().map(|p| p.1).fold(f32::NEG_INFINITY, f32::max).ceil();
+    if !(max_x - min_x <= img_width as f32 * MAX_PHOTO_GROWTH && max_y - min_y <= img_height as f32 * MAX_PHOTO_GROWTH) {
+        return None;
+    }
+
+    let shift = Homography::translation(min_x, min_y);
+    let to_source = shift.then(&to_source);
+    let corrected = warp(img, &to_source, (max_x - min_x) as u32 + 1, (max_y - min_y) as u32 + 1);
+    Some((corrected, to_source))
+}
@@ -0,0 +1,242 @@
+//! Structure detection for tables without gridlines.
+//!
+//! Rows and columns are separated by whitespace: rows by blank scanlines
+//! between lines of text, and columns by vertical gutters that stay
+//! empty in nearly every row. Both are found from ink projection profiles.
+
+use image::{DynamicImage, GenericImage, Rgba};
+
+use {Cell, Table};
+
+/// The minimum number of blank scanlines that separates two rows.
+const MIN_ROW_GAP_PX: u32 = 2;
+
+/// Runs of ink shorter than this many scanlines are noise, not text.
+const MIN_TEXT_HEIGHT_PX: u32 = 3;
+
+/// The minimum width of a gutter between columns, as a fraction of the
+/// typical text height. Narrower gaps are between letters or words.
+const MIN_GUTTER_RATIO: f32 = 0.5;
+
+/// A gutter may still contain ink in this fraction of rows,
+/// which lets titles and long headers cross it.
+const GUTTER_INKED_ROW_FRACTION: f32 = 0.125;
+
+
+/// Given the feature map and the line map, find the ink that is not part
+/// of a line, as a row-major bitmap.
+fn text_ink(features: &DynamicImage, lines: &DynamicImage) -> Vec<bool> {
+    let (width, height) = features.dimensions();
+    let black = Rgba([0,0,0,255]);
+
+    let mut ink = vec![false; (width * height) as usize];
+    for (x, y, px) in features.pixels() {
+        ink[(y * width + x) as usize] = px != black && lines.get_pixel(x, y) == black;
+    }
+    ink
+}
+
+
+/// Finds the vertical extent of each line of text, as (top, bottom) inclusive.
+fn detect_text_rows(ink: &[bool], width: u32, height: u32) -> Vec<(u32, u32)> {
+    let inked = |y: u32| (0 .. width).any(|x| ink[(y * width + x) as usize]);
+
+    let mut runs: Vec<(u32, u32)> = Vec::new();
+    let mut y = 0;
+    while y < height {
+        if !inked(y) {
+            y += 1;
+            continue;
+        }
+
+        let top = y;
+        while y < height && inked(y) {
+            y += 1;
+        }
+        let bottom = y - 1;
+
+        // Too small a gap means the same line of text, such as a
+        // descender that was cut off from its letter.
+        match runs.last_mut() {
+            Some(last) if top - last.1 - 1 < MIN_ROW_GAP_PX => last.1 = bottom,
+            _ => runs.push((top, bottom)),
+        }
+    }
+
+    runs.into_iter()
+        .filter(|&(top, bottom)| bottom - top + 1 >= MIN_TEXT_HEIGHT_PX)
+        .collect()
+}
+
+
+/// Whether the text row has any ink at column x.
+fn row_has_ink_at(ink: &[bool], width: u32, row: (u32, u32), x: u32) -> bool {
+    (row.0 ..= row.1).any(|y| ink[(y * width + x) as usize])
+}
+
+
+/// Finds separators between columns, as (x, gutter width).
+fn detect_gutters(ink: &[bool], width: u32, rows: &[(u32, u32)]) -> Vec<(u32, u32)> {
+    // For each x, the number of text rows with ink there.
+    let inked_rows: Vec<u32> = (0 .. width)
+        .map(|x| rows.iter().filter(|&&row| row_has_ink_at(ink, width, row, x)).count() as u32)
+        .collect();
+
+    let allowance = (rows.len() as f32 * GUTTER_INKED_ROW_FRACTION) as u32;
+
+    let mut heights: Vec<u32> = rows.iter().map(|&(top, bottom)| bottom - top + 1).collect();
+    heights.sort();
+    let text_height = heights.get(heights.len() / 2).cloned().unwrap_or(0);
+    let min_gutter = u32::max((text_height as f32 * MIN_GUTTER_RATIO) as u32, 1);
+
+    let mut acc = Vec::new();
+    let mut x = 0;
+    while x < width {
+        if inked_rows[x as usize] > allowance {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && inked_rows[x as usize] <= allowance {
+            x += 1;
+        }
+        let end = x;
+
+        // Blank margins at the image edge are not between columns.
+        if start == 0 || end == width || end - start < min_gutter {
+            continue;
+        }
+
+        // Cut where the fewest rows have ink, in the middle of the
+        // widest such stretch. This avoids cutting through a title.
+        let least = (start .. end).map(|k| inked_rows[k as usize]).min().unwrap();
+        let mut best = (start, 0);
+        let mut k = start;
+        while k < end {
+            if inked_rows[k as usize] != least {
+                k += 1;
+                continue;
+            }
+            let run_start = k;
+            while k < end && inked_rows[k as usize] == least {
+                k += 1;
+            }
+            if k - run_start > best.1 {
+                best = (run_start, k - run_start);
+            }
+        }
+
+        acc.push((best.0 + best.1 / 2, end - start));
+    }
+
+    acc
+}
+
+
+/// Removes separators until every column has ink in at least half of
+/// the rows. Sparse columns come from gaps inside a column, such as
+/// between a long header and the short numbers beneath it.
+/// Each sparse column is joined to the neighbor across the narrower gutter.
+fn prune_gutters(ink: &[bool], width: u32, rows: &[(u32, u32)], mut gutters: Vec<(u32, u32)>) -> Vec<u32> {
+    loop {
+        let bounds: Vec<u32> = ::std::iter::once(0)
+            .chain(gutters.iter().map(|&(x, _)| x))
+            .chain(::std::iter::once(width))
+            .collect();
+
+        // Find the column with ink in the fewest rows.
+        let sparsest = (0 .. bounds.len() - 1)
+            .map(|c| {
+                let inked = rows.iter()
+                    .filter(|&&row| (bounds[c] .. bounds[c + 1]).any(|x| row_has_ink_at(ink, width, row, x)))
+                    .count();
+                (inked, c)
+            })
+            .min();
+
+        let (inked, col) = match sparsest {
+            Some(found) => found,
+            None => break,
+        };
+        if gutters.is_empty() || inked * 2 >= rows.len() {
+            break;
+        }
+
+        // Column c lies between gutters c - 1 and c.
+        let remove = if col == 0 {
+            0
+        } else if col == gutters.len() || gutters[col - 1].1 < gutters[col].1 {
+            col - 1
+        } else {
+            col
+        };
+        gutters.remove(remove);
+    }
+
+    gutters.into_iter().map(|(x, _)| x).collect()
+}
+
+
+/// Builds a Table from whitespace alone. Gridlines, if there are any,
+/// are ignored rather than treated as separators.
+pub fn detect_cells(features: &DynamicImage, lines: &DynamicImage) -> Table {
+    let (width, height) = features.dimensions();
+    let ink = text_ink(features, lines);
+
+    let text_rows = detect_text_rows(&ink, width, height);
+    let gutters = detect_gutters(&ink, width, &text_rows);
+    let separators = prune_gutters(&ink, width, &text_rows, gutters);
+    let cols = separators.len() as u32 + 1;
+
+    // Rows are separated halfway between lines of text.
+    let row_separators: Vec<u32> = text_rows.windows(2)
+        .map(|pair| (pair[0].1 + pair[1].0) / 2)
+        .collect();
+
+    let mut acc = Vec::<Cell>::new();
+    for (cur_row, &text_row) in text_rows.iter().enumerate() {
+        let cell_y = if cur_row == 0 { 0 } else { row_separators[cur_row - 1] + 1 };
+        let y_bottom = row_separators.get(cur_row).cloned().unwrap_or(height);
+        let cell_height = y_bottom - cell_y;
+
+        let mut cur_col: u32 = 0;
+        let mut prev_x = 0;
+
+        for (i, &x) in separators.iter().enumerate() {
+            // Text that runs across the gutter merges the cells on either side.
+            if row_has_ink_at(&ink, width, text_row, x) {
+                continue;
+            }
+
+            acc.push(Cell {
+                row: cur_row as u32,
+                col: cur_col,
+                row_span: 1,
+                col_span: i as u32 + 1 - cur_col,
+                x: prev_x,
+                y: cell_y,
+                width: x - prev_x,
+                height: cell_height,
+                structure: 1.0,
+            });
+
+            prev_x = x + 1;
+            cur_col = i as u32 + 1;
+        }
+
+        acc.push(Cell {
+            row: cur_row as u32,
+            col: cur_col,
+            row_span: 1,
+            col_span: cols - cur_col,
+            x: prev_x,
+            y: cell_y,
+            width: width - prev_x,
+            height: cell_height,
+            structure: 1.0,
+        });
+    }
+
+    Table::new(row_separators, separators, text_rows.len() as u32, cols, acc)
+}
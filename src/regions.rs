@@ -0,0 +1,136 @@
+//! The regions of an image that hold separate tables.
+//!
+//! A results sheet may stack several tables, or set them side by side.
+//! The gridlines of one table are all joined up, so each table is a
+//! connected area of lines, apart from small breaks in the lines. Text
+//! just outside the lines, such as a title above them, goes with it.
+
+use image::{DynamicImage, GenericImage, Rgba};
+
+use geometry;
+use table::Bounds;
+
+/// Areas of lines closer than this are parts of the same table.
+const MIN_TABLE_GAP_PX: u32 = 10;
+
+/// Features this close to a grid belong with it, as a title over it does.
+/// Text in a cell is never further from its gridlines than this.
+const MAX_TEXT_GAP_PX: u32 = 16;
+
+/// Blank space kept around each region, as in a rectified image.
+const MARGIN_PX: u32 = 2;
+
+
+/// Finds the bounds of each grid of lines, in reading order, along with
+/// any features next to it. A grid must be as tall and as wide as the
+/// shortest line, `line_min_length` pixels.
+pub fn find(features: &DynamicImage, lines: &DynamicImage, line_min_length: u32) -> Vec<Bounds> {
+    let (width, height) = lines.dimensions();
+    let black = Rgba([0, 0, 0, 255]);
+    let mask: Vec<bool> = lines.to_rgba().pixels().map(|&px| px != black).collect();
+    let ink: Vec<bool> = features.to_rgba().pixels().map(|&px| px != black).collect();
+
+    let mut boxes: Vec<Bounds> = geometry::bounding_boxes(&mask, width, height).into_iter()
+        .map(|(left, top, right, bottom)| {
+            Bounds { x: left, y: top, width: right - left + 1, height: bottom - top + 1 }
+        })
+        .collect();
+
+    // Joins areas that are close together until no two are.
+    let mut merged = true;
+    while merged {
+        merged = false;
+        'outer: for i in 0 .. boxes.len() {
+            for j in i + 1 .. boxes.len() {
+                if gap(&boxes[i], &boxes[j]) < MIN_TABLE_GAP_PX {
+                    let other = boxes.swap_remove(j);
+                    boxes[i] = union(&boxes[i], &other);
+                    merged = true;
+                    break 'outer;
+                }
+            }
+        }
+    }
+
+    boxes.retain(|b| b.width >= line_min_length && b.height >= line_min_length);
+    let mut regions: Vec<Bounds> = boxes.iter()
+        .map(|b| expand(&grow(b, &ink, width, height), width, height))
+        .collect();
+
+    // Tables side by side share a band of rows, and are read left to right.
+    regions.sort_by_key(|b| b.y);
+    let mut ordered = Vec::with_capacity(regions.len());
+    let mut start = 0;
+    while start < regions.len() {
+        let mut bottom = regions[start].bottom();
+        let mut end = start + 1;
+        while end < regions.len() && regions[end].y < bottom {
+            bottom = u32::max(bottom, regions[end].bottom());
+            end += 1;
+        }
+
+        let mut band = regions[start .. end].to_vec();
+        band.sort_by_key(|b| b.x);
+        ordered.extend(band);
+        start = end;
+    }
+
+    ordered
+}
+
+
+/// Grows the box to take in features that are less than MAX_TEXT_GAP_PX
+/// away from it, and then features near those, and so on.
+fn grow(b: &Bounds, ink: &[bool], width: u32, height: u32) -> Bounds {
+    let (mut left, mut top, mut right, mut bottom) = (b.x, b.y, b.right(), b.bottom());
+    let row_has_ink = |y: u32, left: u32, right: u32| (left .. right).any(|x| ink[(y * width + x) as usize]);
+    let col_has_ink = |x: u32, top: u32, bottom: u32| (top .. bottom).any(|y| ink[(y * width + x) as usize]);
+
+    let mut grown = true;
+    while grown {
+        grown = false;
+        if let Some(y) = (top.saturating_sub(MAX_TEXT_GAP_PX) .. top).find(|&y| row_has_ink(y, left, right)) {
+            top = y;
+            grown = true;
+        }
+        if let Some(y) = (bottom .. u32::min(bottom + MAX_TEXT_GAP_PX, height)).rev().find(|&y| row_has_ink(y, left, right)) {
+            bottom = y + 1;
+            grown = true;
+        }
+        if let Some(x) = (left.saturating_sub(MAX_TEXT_GAP_PX) .. left).find(|&x| col_has_ink(x, top, bottom)) {
+            left = x;
+            grown = true;
+        }
+        if let Some(x) = (right .. u32::min(right + MAX_TEXT_GAP_PX, width)).rev().find(|&x| col_has_ink(x, top, bottom)) {
+            right = x + 1;
+            grown = true;
+        }
+    }
+
+    Bounds { x: left, y: top, width: right - left, height: bottom - top }
+}
+
+
+/// The number of pixels between two boxes, or zero if they touch or overlap.
+fn gap(a: &Bounds, b: &Bounds) -> u32 {
+    let dx = u32::max(a.x.saturating_sub(b.right()), b.x.saturating_sub(a.right()));
+    let dy = u32::max(a.y.saturating_sub(b.bottom()), b.y.saturating_sub(a.bottom()));
+    u32::max(dx, dy)
+}
+
+
+/// The smallest box that covers both boxes.
+fn union(a: &Bounds, b: &Bounds) -> Bounds {
+    let (x, y) = (u32::min(a.x, b.x), u32::min(a.y, b.y));
+    let (right, bottom) = (u32::max(a.right(), b.right()), u32::max(a.bottom(), b.bottom()));
+    Bounds { x, y, width: right - x, height: bottom - y }
+}
+
+
+/// Grows the box by the margin on every side, within the image.
+fn expand(b: &Bounds, width: u32, height: u32) -> Bounds {
+    let (x, y) = (b.x.saturating_sub(MARGIN_PX), b.y.saturating_sub(MARGIN_PX));
+    let right = u32::min(b.right() + MARGIN_PX, width);
+    let bottom = u32::min(b.bottom() + MARGIN_PX, height);
+    Bounds { x, y, width: right - x, height: bottom - y }
+}
@@ -0,0 +1,171 @@
+//! A self-contained HTML page for proofreading recognized tables.
+//!
+//! Each table is rebuilt as an HTML table whose cells show their crop
+//! above the recognized text, colored from red to green by confidence.
+//! Clicking a cell outlines where it is on a thumbnail of the source
+//! image. Images are embedded as data URIs, so the page is one file.
+
+use std::io::{self, Write};
+
+use image::{self, DynamicImage, FilterType, GenericImage};
+
+use table::{Bounds, Table};
+use Cell;
+
+/// The widest the thumbnail of the source image is drawn, in pixels.
+const THUMBNAIL_WIDTH_PX: u32 = 800;
+
+const STYLE: &str = "\
+body { font-family: sans-serif; margin: 0; }
+#source { position: sticky; top: 0; background: #fff; padding: 8px; border-bottom: 1px solid #888; }
+#source div { position: relative; display: inline-block; }
+#marker { position: absolute; border: 2px solid #e00; display: none; pointer-events: none; }
+table { border-collapse: collapse; margin: 8px; }
+caption { text-align: left; font-weight: bold; }
+td { border: 1px solid #888; padding: 2px; vertical-align: top; cursor: pointer; }
+td img { display: block; }
+td.selected { outline: 3px solid #e00; }
+td span { font-family: monospace; white-space: pre; }";
+
+const SCRIPT: &str = "\
+var selected = null;
+document.addEventListener('click', function (event) {
+  var td = event.target.closest('td');
+  if (!td) return;
+  if (selected) selected.classList.remove('selected');
+  selected = td;
+  td.classList.add('selected');
+  var thumbnail = document.getElementById('thumbnail');
+  var scale = thumbnail.width / thumbnail.dataset.width;
+  var marker = document.getElementById('marker');
+  marker.style.left = (td.dataset.x * scale - 2) + 'px';
+  marker.style.top = (td.dataset.y * scale - 2) + 'px';
+  marker.style.width = (td.dataset.width * scale) + 'px';
+  marker.style.height = (td.dataset.height * scale) + 'px';
+  marker.style.display = 'block';
+});";
+
+
+/// A recognized cell, as it is shown in the report.
+pub struct Entry {
+    pub cell: Cell,
+    /// Where the cell is in the source image.
+    pub bounds: Bounds,
+    /// The cell as it looks in the source image.
+    pub crop: DynamicImage,
+    pub text: String,
+    /// From 0.0 to 1.0, as in `confidence::combine`.
+    pub confidence: f32,
+}
+
+
+/// An HTML report on the tables of one image.
+pub struct Report {
+    title: String,
+    source_width: u32,
+    thumbnail: DynamicImage,
+    /// Each table's size in rows and columns, and its entries.
+    tables: Vec<(u32, u32, Vec<Entry>)>,
+}
+
+impl Report {
+    /// Starts a report on the given source image.
+    pub fn new(title: &str, source: &DynamicImage) -> Report {
+        let (width, height) = source.dimensions();
+        let thumbnail = if width > THUMBNAIL_WIDTH_PX {
+            let thumbnail_height = u32::max(height * THUMBNAIL_WIDTH_PX / width, 1);
+            source.resize_exact(THUMBNAIL_WIDTH_PX, thumbnail_height, FilterType::Triangle)
+        } else {
+            source.clone()
+        };
+        Report { title: title.to_string(), source_width: width, thumbnail, tables: Vec::new() }
+    }
+
+    /// Adds a table, with an entry for each of its cells.
+    pub fn add_table(&mut self, table: &Table, entries: Vec<Entry>) {
+        self.tables.push((table.rows(), table.cols(), entries));
+    }
+
+    /// Writes the report as a single HTML page.
+    pub fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        write!(w, "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n")?;
+        write!(w, "<title>{}</title>\n<style>\n{}\n</style>\n</head>\n<body>\n", escape(&self.title), STYLE)?;
+
+        write!(w, "<div id=\"source\"><div>")?;
+        write!(w, "<img id=\"thumbnail\" data-width=\"{}\" src=\"{}\">", self.source_width, data_uri(&self.thumbnail)?)?;
+        writeln!(w, "<div id=\"marker\"></div></div></div>")?;
+
+        for (i, &(rows, cols, ref entries)) in self.tables.iter().enumerate() {
+            write!(w, "<table>\n<caption>Table {}: {} rows, {} cols</caption>\n", i + 1, rows, cols)?;
+            for row in 0 .. rows {
+                write!(w, "<tr>")?;
+                for entry in entries.iter().filter(|e| e.cell.row == row) {
+                    write_cell(w, entry)?;
+                }
+                writeln!(w, "</tr>")?;
+            }
+            writeln!(w, "</table>")?;
+        }
+
+        write!(w, "<script>\n{}\n</script>\n</body>\n</html>\n", SCRIPT)
+    }
+}
+
+
+/// Writes one cell of a table, with its crop above its text.
+fn write_cell<W: Write>(w: &mut W, entry: &Entry) -> io::Result<()> {
+    let (cell, b) = (&entry.cell, &entry.bounds);
+    write!(w, "<td rowspan=\"{}\" colspan=\"{}\" style=\"background: {}\" title=\"confidence {:.2}\"",
+           cell.row_span, cell.col_span, color(entry.confidence), entry.confidence)?;
+    write!(w, " data-x=\"{}\" data-y=\"{}\" data-width=\"{}\" data-height=\"{}\">", b.x, b.y, b.width, b.height)?;
+    write!(w, "<img src=\"{}\"><span>{}</span></td>", data_uri(&entry.crop)?, escape(&entry.text))
+}
+
+
+/// A pale background color for a confidence, from red at 0.0 to green at 1.0.
+fn color(confidence: f32) -> String {
+    let hue = (confidence.clamp(0.0, 1.0) * 120.0).round();
+    format!("hsl({}, 70%, 85%)", hue)
+}
+
+
+/// Escapes text for HTML content and attribute values.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+
+/// The image as a PNG data URI.
+fn data_uri(img: &DynamicImage) -> io::Result<String> {
+    let mut png = Vec::new();
+    img.save(&mut png, image::PNG).map_err(io::Error::other)?;
+    Ok(format!("data:image/png;base64,{}", base64(&png)))
+}
+
+
+/// Encodes bytes as standard base64, with padding.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0 .. 4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,625 @@
+//! Outlines of a real typeface, DejaVu Sans, drawn at any size.
+//!
+//! The bitmap `font` puts every glyph on the same 5 by 9 grid, as no
+//! spreadsheet does. These are the TrueType contours of DejaVu Sans for
+//! the same characters, and they are drawn with anti-aliasing as a screen
+//! draws them, so that synthetic tables can be lettered in a proportional
+//! typeface and the built-in recognizer has one to match against.
+//!
+//! DejaVu Sans is Copyright (c) 2003 by Bitstream, Inc. All Rights Reserved.
+//! Bitstream Vera is a trademark of Bitstream, Inc. The changes made by the
+//! DejaVu fonts team are in the public domain. The outlines are distributed
+//! under the Bitstream Vera license, whose full text, with its copyright and
+//! permission notice, is in LICENSE-DEJAVU at the root of the crate.
+
+/// Font units in an em. Coordinates and advances are in font units,
+/// with y going up from the baseline.
+pub const UNITS_PER_EM: f32 = 2048.0;
+
+/// The height of the line above the baseline, in font units.
+pub const ASCENT: i16 = 1901;
+
+/// The depth of the line below the baseline, in font units.
+pub const DESCENT: i16 = 483;
+
+/// The height of a capital letter or digit, in font units.
+pub const CAP_HEIGHT: i16 = 1493;
+
+/// The advance of a space, and of characters without an outline.
+const SPACE_ADVANCE: u16 = 651;
+
+/// Straight segments that each curve is drawn with.
+const CURVE_STEPS: u32 = 8;
+
+/// Scanlines sampled in each row of pixels. Coverage along a scanline is exact.
+const SUBSCANLINES: u32 = 4;
+
+/// The outline of a single character.
+pub struct Outline {
+    pub ch: char,
+    /// How far the pen moves after the character, in font units.
+    pub advance: u16,
+    /// Closed contours of (x, y, on) points, where `on` is 1 for points on
+    /// the curve and 0 for the control points of quadratic curves between them.
+    pub contours: &'static [&'static [(i16, i16, u8)]],
+}
+
+/// Looks up the outline of a character, if the typeface has one.
+pub fn outline(ch: char) -> Option<&'static Outline> {
+    OUTLINES.iter().find(|o| o.ch == ch)
+}
+
+/// Pixels per font unit at `size` pixels to the em.
+fn scale(size: f32) -> f32 {
+    size / UNITS_PER_EM
+}
+
+/// The width of `text` at `size` pixels to the em, rounded up.
+pub fn text_width(text: &str, size: f32) -> u32 {
+    let units: u32 = text.chars().map(|ch| outline(ch).map_or(SPACE_ADVANCE, |o| o.advance) as u32).sum();
+    (units as f32 * scale(size)).ceil() as u32
+}
+
+/// The height of a line of text at `size` pixels to the em, rounded up.
+pub fn line_height(size: f32) -> u32 {
+    ((ASCENT + DESCENT) as f32 * scale(size)).ceil() as u32
+}
+
+/// How much of each pixel is covered by ink, from 0.0 to 1.0.
+pub struct Coverage {
+    pub width: u32,
+    pub height: u32,
+    values: Vec<f32>,
+}
+
+impl Coverage {
+    /// The coverage of the pixel at (x, y).
+    pub fn at(&self, x: u32, y: u32) -> f32 {
+        self.values[(y * self.width + x) as usize]
+    }
+}
+
+/// Draws `text` at `size` pixels to the em, with the top of the line at
+/// the top of the image and the baseline `ASCENT` font units below it.
+/// The image is `text_width` by `line_height` pixels.
+pub fn render(text: &str, size: f32) -> Coverage {
+    let s = scale(size);
+    let (width, height) = (text_width(text, size), line_height(size));
+
+    // Every contour as straight edges from (x0, y0) to (x1, y1), in pixels.
+    let mut edges: Vec<(f32, f32, f32, f32)> = Vec::new();
+    let mut pen = 0.0;
+    for ch in text.chars() {
+        let outline = match outline(ch) {
+            Some(outline) => outline,
+            None => {
+                pen += SPACE_ADVANCE as f32 * s;
+                continue;
+            }
+        };
+        let to_pixels = |x: f32, y: f32| (pen + x * s, (ASCENT as f32 - y) * s);
+        for contour in outline.contours {
+            let points = flatten(contour);
+            for (i, &(x0, y0)) in points.iter().enumerate() {
+                let (x1, y1) = points[(i + 1) % points.len()];
+                let (a, b) = (to_pixels(x0, y0), to_pixels(x1, y1));
+                edges.push((a.0, a.1, b.0, b.1));
+            }
+        }
+        pen += outline.advance as f32 * s;
+    }
+
+    // Fills between crossings of each scanline where the winding is not zero.
+    let mut values = vec![0.0; (width * height) as usize];
+    let mut crossings: Vec<(f32, i32)> = Vec::new();
+    for row in 0 .. height {
+        for sub in 0 .. SUBSCANLINES {
+            let y = row as f32 + (sub as f32 + 0.5) / SUBSCANLINES as f32;
+            crossings.clear();
+            for &(x0, y0, x1, y1) in &edges {
+                if (y0 <= y) != (y1 <= y) {
+                    let x = x0 + (y - y0) / (y1 - y0) * (x1 - x0);
+                    crossings.push((x, if y1 > y0 { 1 } else { -1 }));
+                }
+            }
+            crossings.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+            let mut winding = 0;
+            for pair in crossings.windows(2) {
+                winding += pair[0].1;
+                if winding != 0 {
+                    add_span(&mut values[(row * width) as usize .. ((row + 1) * width) as usize],
+                             pair[0].0, pair[1].0, 1.0 / SUBSCANLINES as f32);
+                }
+            }
+        }
+    }
+
+    Coverage { width, height, values }
+}
+
+/// The points along a closed TrueType contour, with its curves cut into
+/// straight segments. Between two control points lies an implied point
+/// on the curve, halfway between them.
+fn flatten(contour: &[(i16, i16, u8)]) -> Vec<(f32, f32)> {
+    let n = contour.len();
+    let point = |i: usize| {
+        let (x, y, on) = contour[i % n];
+        (x as f32, y as f32, on == 1)
+    };
+    let midpoint = |a: (f32, f32, bool), b: (f32, f32, bool)| ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0, true);
+
+    // Start on the curve, which may be implied.
+    let first = (0 .. n).find(|&i| point(i).2);
+    let (start, mut prev) = match first {
+        Some(i) => (i, point(i)),
+        None => (0, midpoint(point(n - 1), point(0))),
+    };
+
+    let mut points = vec![(prev.0, prev.1)];
+    let mut control: Option<(f32, f32, bool)> = None;
+    let steps = if first.is_some() { 1 .. n + 1 } else { 0 .. n + 1 };
+    for i in steps {
+        let p = point(start + i);
+        let end = match (control, p.2) {
+            (None, true) => {
+                points.push((p.0, p.1));
+                prev = p;
+                continue;
+            }
+            (None, false) => {
+                control = Some(p);
+                continue;
+            }
+            (Some(_), true) => p,
+            (Some(c), false) => midpoint(c, p),
+        };
+
+        let c = control.unwrap();
+        for k in 1 .. CURVE_STEPS + 1 {
+            let t = k as f32 / CURVE_STEPS as f32;
+            let u = 1.0 - t;
+            points.push((u * u * prev.0 + 2.0 * u * t * c.0 + t * t * end.0,
+                         u * u * prev.1 + 2.0 * u * t * c.1 + t * t * end.1));
+        }
+        prev = end;
+        control = if p.2 { None } else { Some(p) };
+    }
+    points.pop();
+    points
+}
+
+/// Adds `weight` times the part of each pixel between x0 and x1.
+fn add_span(row: &mut [f32], x0: f32, x1: f32, weight: f32) {
+    let (x0, x1) = (x0.max(0.0), x1.min(row.len() as f32));
+    if x1 <= x0 {
+        return;
+    }
+    let (first, last) = (x0.floor() as usize, x1.ceil() as usize);
+    for (x, value) in row.iter_mut().enumerate().take(last).skip(first) {
+        let covered = f32::min(x1, x as f32 + 1.0) - f32::max(x0, x as f32);
+        *value += weight * covered;
+    }
+}
+
+/// Every outline, in the same order as the glyphs of `font`.
+pub static OUTLINES: &[Outline] = &[
+    Outline { ch: '0', advance: 1303, contours: &[
+        &[(651, 1360, 1), (495, 1360, 0), (338, 1053, 0), (338, 745, 1), (338, 438, 0), (495, 131, 0),
+          (651, 131, 1), (808, 131, 0), (965, 438, 0), (965, 745, 1), (965, 1053, 0), (808, 1360, 0)],
+        &[(651, 1520, 1), (902, 1520, 0), (1167, 1123, 0), (1167, 745, 1), (1167, 368, 0), (902, -29, 0),
+          (651, -29, 1), (400, -29, 0), (135, 368, 0), (135, 745, 1), (135, 1123, 0), (400, 1520, 0)],
+    ] },
+    Outline { ch: '1', advance: 1303, contours: &[
+        &[(254, 170, 1), (584, 170, 1), (584, 1309, 1), (225, 1237, 1), (225, 1421, 1), (582, 1493, 1),
+          (784, 1493, 1), (784, 170, 1), (1114, 170, 1), (1114, 0, 1), (254, 0, 1)],
+    ] },
+    Outline { ch: '2', advance: 1303, contours: &[
+        &[(393, 170, 1), (1098, 170, 1), (1098, 0, 1), (150, 0, 1), (150, 170, 1), (265, 289, 0),
+          (662, 690, 0), (713, 748, 1), (810, 857, 0), (887, 1008, 0), (887, 1081, 1), (887, 1200, 0),
+          (720, 1350, 0), (586, 1350, 1), (491, 1350, 0), (280, 1284, 0), (160, 1217, 1), (160, 1421, 1),
+          (282, 1470, 0), (494, 1520, 0), (582, 1520, 1), (814, 1520, 0), (1090, 1288, 0), (1090, 1094, 1),
+          (1090, 1002, 0), (1021, 837, 0), (930, 725, 1), (905, 696, 0), (637, 419, 0)],
+    ] },
+    Outline { ch: '3', advance: 1303, contours: &[
+        &[(831, 805, 1), (976, 774, 0), (1139, 578, 0), (1139, 434, 1), (1139, 213, 0), (835, -29, 0),
+          (555, -29, 1), (461, -29, 0), (262, 8, 0), (156, 45, 1), (156, 240, 1), (240, 191, 0),
+          (440, 141, 0), (549, 141, 1), (739, 141, 0), (938, 291, 0), (938, 434, 1), (938, 566, 0),
+          (753, 715, 0), (588, 715, 1), (414, 715, 1), (414, 881, 1), (596, 881, 1), (745, 881, 0),
+          (903, 1000, 0), (903, 1112, 1), (903, 1227, 0), (740, 1350, 0), (588, 1350, 1), (505, 1350, 0),
+          (315, 1314, 0), (201, 1276, 1), (201, 1456, 1), (316, 1488, 0), (517, 1520, 0), (606, 1520, 1),
+          (836, 1520, 0), (1104, 1311, 0), (1104, 1133, 1), (1104, 1009, 0), (962, 838, 0)],
+    ] },
+    Outline { ch: '4', advance: 1303, contours: &[
+        &[(774, 1317, 1), (264, 520, 1), (774, 520, 1)],
+        &[(721, 1493, 1), (975, 1493, 1), (975, 520, 1), (1188, 520, 1), (1188, 352, 1), (975, 352, 1),
+          (975, 0, 1), (774, 0, 1), (774, 352, 1), (100, 352, 1), (100, 547, 1)],
+    ] },
+    Outline { ch: '5', advance: 1303, contours: &[
+        &[(221, 1493, 1), (1014, 1493, 1), (1014, 1323, 1), (406, 1323, 1), (406, 957, 1), (450, 972, 0),
+          (538, 987, 0), (582, 987, 1), (832, 987, 0), (1124, 713, 0), (1124, 479, 1), (1124, 238, 0),
+          (824, -29, 0), (551, -29, 1), (457, -29, 0), (262, 3, 0), (158, 35, 1), (158, 238, 1),
+          (248, 189, 0), (440, 141, 0), (547, 141, 1), (720, 141, 0), (922, 323, 0), (922, 479, 1),
+          (922, 635, 0), (720, 817, 0), (547, 817, 1), (466, 817, 0), (305, 781, 0), (221, 743, 1)],
+    ] },
+    Outline { ch: '6', advance: 1303, contours: &[
+        &[(676, 827, 1), (540, 827, 0), (381, 641, 0), (381, 479, 1), (381, 318, 0), (540, 131, 0),
+          (676, 131, 1), (812, 131, 0), (971, 318, 0), (971, 479, 1), (971, 641, 0), (812, 827, 0)],
+        &[(1077, 1460, 1), (1077, 1276, 1), (1001, 1312, 0), (846, 1350, 0), (770, 1350, 1), (570, 1350, 0),
+          (359, 1080, 0), (344, 807, 1), (403, 894, 0), (581, 987, 0), (688, 987, 1), (913, 987, 0),
+          (1174, 714, 0), (1174, 479, 1), (1174, 249, 0), (902, -29, 0), (676, -29, 1), (417, -29, 0),
+          (143, 368, 0), (143, 745, 1), (143, 1099, 0), (479, 1520, 0), (762, 1520, 1), (838, 1520, 0),
+          (993, 1490, 0)],
+    ] },
+    Outline { ch: '7', advance: 1303, contours: &[
+        &[(168, 1493, 1), (1128, 1493, 1), (1128, 1407, 1), (586, 0, 1), (375, 0, 1), (885, 1323, 1),
+          (168, 1323, 1)],
+    ] },
+    Outline { ch: '8', advance: 1303, contours: &[
+        &[(651, 709, 1), (507, 709, 0), (342, 555, 0), (342, 420, 1), (342, 285, 0), (507, 131, 0),
+          (651, 131, 1), (795, 131, 0), (961, 286, 0), (961, 420, 1), (961, 555, 0), (796, 709, 0)],
+        &[(449, 795, 1), (319, 827, 0), (174, 1005, 0), (174, 1133, 1), (174, 1312, 0), (429, 1520, 0),
+          (651, 1520, 1), (874, 1520, 0), (1128, 1312, 0), (1128, 1133, 1), (1128, 1005, 0), (983, 827, 0),
+          (854, 795, 1), (1000, 761, 0), (1163, 563, 0), (1163, 420, 1), (1163, 203, 0), (898, -29, 0),
+          (651, -29, 1), (404, -29, 0), (139, 203, 0), (139, 420, 1), (139, 563, 0), (303, 761, 0)],
+        &[(375, 1114, 1), (375, 998, 0), (520, 868, 0), (651, 868, 1), (781, 868, 0), (928, 998, 0),
+          (928, 1114, 1), (928, 1230, 0), (781, 1360, 0), (651, 1360, 1), (520, 1360, 0), (375, 1230, 0)],
+    ] },
+    Outline { ch: '9', advance: 1303, contours: &[
+        &[(225, 31, 1), (225, 215, 1), (301, 179, 0), (457, 141, 0), (532, 141, 1), (732, 141, 0),
+          (943, 410, 0), (958, 684, 1), (900, 598, 0), (722, 506, 0), (614, 506, 1), (390, 506, 0),
+          (129, 777, 0), (129, 1012, 1), (129, 1242, 0), (401, 1520, 0), (627, 1520, 1), (886, 1520, 0),
+          (1159, 1123, 0), (1159, 745, 1), (1159, 392, 0), (824, -29, 0), (541, -29, 1), (465, -29, 0),
+          (309, 1, 0)],
+        &[(627, 664, 1), (763, 664, 0), (922, 850, 0), (922, 1012, 1), (922, 1173, 0), (763, 1360, 0),
+          (627, 1360, 1), (491, 1360, 0), (332, 1173, 0), (332, 1012, 1), (332, 850, 0), (491, 664, 0)],
+    ] },
+    Outline { ch: 'A', advance: 1401, contours: &[
+        &[(700, 1294, 1), (426, 551, 1), (975, 551, 1)],
+        &[(586, 1493, 1), (815, 1493, 1), (1384, 0, 1), (1174, 0, 1), (1038, 383, 1), (365, 383, 1),
+          (229, 0, 1), (16, 0, 1)],
+    ] },
+    Outline { ch: 'B', advance: 1405, contours: &[
+        &[(403, 713, 1), (403, 166, 1), (727, 166, 1), (890, 166, 0), (1047, 301, 0), (1047, 440, 1),
+          (1047, 580, 0), (890, 713, 0), (727, 713, 1)],
+        &[(403, 1327, 1), (403, 877, 1), (702, 877, 1), (850, 877, 0), (995, 988, 0), (995, 1102, 1),
+          (995, 1215, 0), (850, 1327, 0), (702, 1327, 1)],
+        &[(201, 1493, 1), (717, 1493, 1), (948, 1493, 0), (1198, 1301, 0), (1198, 1124, 1), (1198, 987, 0),
+          (1070, 825, 0), (946, 805, 1), (1095, 773, 0), (1260, 570, 0), (1260, 418, 1), (1260, 218, 0),
+          (988, 0, 0), (737, 0, 1), (201, 0, 1)],
+    ] },
+    Outline { ch: 'C', advance: 1430, contours: &[
+        &[(1319, 1378, 1), (1319, 1165, 1), (1217, 1260, 0), (986, 1354, 0), (856, 1354, 1), (600, 1354, 0),
+          (328, 1041, 0), (328, 745, 1), (328, 450, 0), (600, 137, 0), (856, 137, 1), (986, 137, 0),
+          (1217, 231, 0), (1319, 326, 1), (1319, 115, 1), (1213, 43, 0), (976, -29, 0), (844, -29, 1),
+          (505, -29, 0), (115, 386, 0), (115, 745, 1), (115, 1105, 0), (505, 1520, 0), (844, 1520, 1),
+          (978, 1520, 0), (1215, 1449, 0)],
+    ] },
+    Outline { ch: 'D', advance: 1577, contours: &[
+        &[(403, 1327, 1), (403, 166, 1), (647, 166, 1), (956, 166, 0), (1243, 446, 0), (1243, 748, 1),
+          (1243, 1048, 0), (956, 1327, 0), (647, 1327, 1)],
+        &[(201, 1493, 1), (616, 1493, 1), (1050, 1493, 0), (1456, 1132, 0), (1456, 748, 1), (1456, 362, 0),
+          (1048, 0, 0), (616, 0, 1), (201, 0, 1)],
+    ] },
+    Outline { ch: 'E', advance: 1294, contours: &[
+        &[(201, 1493, 1), (1145, 1493, 1), (1145, 1323, 1), (403, 1323, 1), (403, 881, 1), (1114, 881, 1),
+          (1114, 711, 1), (403, 711, 1), (403, 170, 1), (1163, 170, 1), (1163, 0, 1), (201, 0, 1)],
+    ] },
+    Outline { ch: 'F', advance: 1178, contours: &[
+        &[(201, 1493, 1), (1059, 1493, 1), (1059, 1323, 1), (403, 1323, 1), (403, 883, 1), (995, 883, 1),
+          (995, 713, 1), (403, 713, 1), (403, 0, 1), (201, 0, 1)],
+    ] },
+    Outline { ch: 'G', advance: 1587, contours: &[
+        &[(1219, 213, 1), (1219, 614, 1), (889, 614, 1), (889, 780, 1), (1419, 780, 1), (1419, 139, 1),
+          (1302, 56, 0), (1020, -29, 0), (860, -29, 1), (510, -29, 0), (115, 380, 0), (115, 745, 1),
+          (115, 1111, 0), (510, 1520, 0), (860, 1520, 1), (1006, 1520, 0), (1269, 1448, 0), (1380, 1378, 1),
+          (1380, 1163, 1), (1268, 1258, 0), (1016, 1354, 0), (877, 1354, 1), (603, 1354, 0), (328, 1048, 0),
+          (328, 745, 1), (328, 443, 0), (603, 137, 0), (877, 137, 1), (984, 137, 0), (1152, 174, 0)],
+    ] },
+    Outline { ch: 'H', advance: 1540, contours: &[
+        &[(201, 1493, 1), (403, 1493, 1), (403, 881, 1), (1137, 881, 1), (1137, 1493, 1), (1339, 1493, 1),
+          (1339, 0, 1), (1137, 0, 1), (1137, 711, 1), (403, 711, 1), (403, 0, 1), (201, 0, 1)],
+    ] },
+    Outline { ch: 'I', advance: 604, contours: &[
+        &[(201, 1493, 1), (403, 1493, 1), (403, 0, 1), (201, 0, 1)],
+    ] },
+    Outline { ch: 'J', advance: 604, contours: &[
+        &[(201, 1493, 1), (403, 1493, 1), (403, 104, 1), (403, -166, 0), (198, -410, 0), (-29, -410, 1),
+          (-106, -410, 1), (-106, -240, 1), (-43, -240, 1), (91, -240, 0), (201, -90, 0), (201, 104, 1)],
+    ] },
+    Outline { ch: 'K', advance: 1343, contours: &[
+        &[(201, 1493, 1), (403, 1493, 1), (403, 862, 1), (1073, 1493, 1), (1333, 1493, 1), (592, 797, 1),
+          (1386, 0, 1), (1120, 0, 1), (403, 719, 1), (403, 0, 1), (201, 0, 1)],
+    ] },
+    Outline { ch: 'L', advance: 1141, contours: &[
+        &[(201, 1493, 1), (403, 1493, 1), (403, 170, 1), (1130, 170, 1), (1130, 0, 1), (201, 0, 1)],
+    ] },
+    Outline { ch: 'M', advance: 1767, contours: &[
+        &[(201, 1493, 1), (502, 1493, 1), (883, 477, 1), (1266, 1493, 1), (1567, 1493, 1), (1567, 0, 1),
+          (1370, 0, 1), (1370, 1311, 1), (985, 287, 1), (782, 287, 1), (397, 1311, 1), (397, 0, 1),
+          (201, 0, 1)],
+    ] },
+    Outline { ch: 'N', advance: 1532, contours: &[
+        &[(201, 1493, 1), (473, 1493, 1), (1135, 244, 1), (1135, 1493, 1), (1331, 1493, 1), (1331, 0, 1),
+          (1059, 0, 1), (397, 1249, 1), (397, 0, 1), (201, 0, 1)],
+    ] },
+    Outline { ch: 'O', advance: 1612, contours: &[
+        &[(807, 1356, 1), (587, 1356, 0), (328, 1028, 0), (328, 745, 1), (328, 463, 0), (587, 135, 0),
+          (807, 135, 1), (1027, 135, 0), (1284, 463, 0), (1284, 745, 1), (1284, 1028, 0), (1027, 1356, 0)],
+        &[(807, 1520, 1), (1121, 1520, 0), (1497, 1099, 0), (1497, 745, 1), (1497, 392, 0), (1121, -29, 0),
+          (807, -29, 1), (492, -29, 0), (115, 391, 0), (115, 745, 1), (115, 1099, 0), (492, 1520, 0)],
+    ] },
+    Outline { ch: 'P', advance: 1235, contours: &[
+        &[(403, 1327, 1), (403, 766, 1), (657, 766, 1), (798, 766, 0), (952, 912, 0), (952, 1047, 1),
+          (952, 1181, 0), (798, 1327, 0), (657, 1327, 1)],
+        &[(201, 1493, 1), (657, 1493, 1), (908, 1493, 0), (1165, 1266, 0), (1165, 1047, 1), (1165, 826, 0),
+          (908, 600, 0), (657, 600, 1), (403, 600, 1), (403, 0, 1), (201, 0, 1)],
+    ] },
+    Outline { ch: 'Q', advance: 1612, contours: &[
+        &[(807, 1356, 1), (587, 1356, 0), (328, 1028, 0), (328, 745, 1), (328, 463, 0), (587, 135, 0),
+          (807, 135, 1), (1027, 135, 0), (1284, 463, 0), (1284, 745, 1), (1284, 1028, 0), (1027, 1356, 0)],
+        &[(1090, 27, 1), (1356, -264, 1), (1112, -264, 1), (891, -25, 1), (858, -27, 0), (823, -29, 0),
+          (807, -29, 1), (492, -29, 0), (115, 392, 0), (115, 745, 1), (115, 1099, 0), (492, 1520, 0),
+          (807, 1520, 1), (1121, 1520, 0), (1497, 1099, 0), (1497, 745, 1), (1497, 485, 0), (1288, 115, 0)],
+    ] },
+    Outline { ch: 'R', advance: 1423, contours: &[
+        &[(909, 700, 1), (974, 678, 0), (1097, 534, 0), (1159, 408, 1), (1364, 0, 1), (1147, 0, 1),
+          (956, 383, 1), (882, 533, 0), (743, 631, 0), (623, 631, 1), (403, 631, 1), (403, 0, 1),
+          (201, 0, 1), (201, 1493, 1), (657, 1493, 1), (913, 1493, 0), (1165, 1279, 0), (1165, 1063, 1),
+          (1165, 922, 0), (1034, 736, 0)],
+        &[(403, 1327, 1), (403, 797, 1), (657, 797, 1), (803, 797, 0), (952, 932, 0), (952, 1063, 1),
+          (952, 1194, 0), (803, 1327, 0), (657, 1327, 1)],
+    ] },
+    Outline { ch: 'S', advance: 1300, contours: &[
+        &[(1096, 1444, 1), (1096, 1247, 1), (981, 1302, 0), (777, 1356, 0), (682, 1356, 1), (517, 1356, 0),
+          (338, 1228, 0), (338, 1110, 1), (338, 1011, 0), (457, 910, 0), (623, 879, 1), (745, 854, 1),
+          (971, 811, 0), (1186, 594, 0), (1186, 412, 1), (1186, 195, 0), (895, -29, 0), (614, -29, 1),
+          (508, -29, 0), (269, 19, 0), (141, 66, 1), (141, 274, 1), (264, 205, 0), (500, 135, 0),
+          (614, 135, 1), (787, 135, 0), (975, 271, 0), (975, 397, 1), (975, 507, 0), (840, 631, 0),
+          (686, 662, 1), (563, 686, 1), (337, 731, 0), (135, 923, 0), (135, 1094, 1), (135, 1292, 0),
+          (414, 1520, 0), (659, 1520, 1), (764, 1520, 0), (982, 1482, 0)],
+    ] },
+    Outline { ch: 'T', advance: 1251, contours: &[
+        &[(-6, 1493, 1), (1257, 1493, 1), (1257, 1323, 1), (727, 1323, 1), (727, 0, 1), (524, 0, 1),
+          (524, 1323, 1), (-6, 1323, 1)],
+    ] },
+    Outline { ch: 'U', advance: 1499, contours: &[
+        &[(178, 1493, 1), (381, 1493, 1), (381, 586, 1), (381, 346, 0), (555, 135, 0), (750, 135, 1),
+          (944, 135, 0), (1118, 346, 0), (1118, 586, 1), (1118, 1493, 1), (1321, 1493, 1), (1321, 561, 1),
+          (1321, 269, 0), (1032, -29, 0), (750, -29, 1), (467, -29, 0), (178, 269, 0), (178, 561, 1)],
+    ] },
+    Outline { ch: 'V', advance: 1401, contours: &[
+        &[(586, 0, 1), (16, 1493, 1), (227, 1493, 1), (700, 236, 1), (1174, 1493, 1), (1384, 1493, 1),
+          (815, 0, 1)],
+    ] },
+    Outline { ch: 'W', advance: 2025, contours: &[
+        &[(68, 1493, 1), (272, 1493, 1), (586, 231, 1), (899, 1493, 1), (1126, 1493, 1), (1440, 231, 1),
+          (1753, 1493, 1), (1958, 1493, 1), (1583, 0, 1), (1329, 0, 1), (1014, 1296, 1), (696, 0, 1),
+          (442, 0, 1)],
+    ] },
+    Outline { ch: 'X', advance: 1403, contours: &[
+        &[(129, 1493, 1), (346, 1493, 1), (717, 938, 1), (1090, 1493, 1), (1307, 1493, 1), (827, 776, 1),
+          (1339, 0, 1), (1122, 0, 1), (702, 635, 1), (279, 0, 1), (61, 0, 1), (594, 797, 1)],
+    ] },
+    Outline { ch: 'Y', advance: 1251, contours: &[
+        &[(-4, 1493, 1), (213, 1493, 1), (627, 879, 1), (1038, 1493, 1), (1255, 1493, 1), (727, 711, 1),
+          (727, 0, 1), (524, 0, 1), (524, 711, 1)],
+    ] },
+    Outline { ch: 'Z', advance: 1403, contours: &[
+        &[(115, 1493, 1), (1288, 1493, 1), (1288, 1339, 1), (344, 170, 1), (1311, 170, 1), (1311, 0, 1),
+          (92, 0, 1), (92, 154, 1), (1036, 1323, 1), (115, 1323, 1)],
+    ] },
+    Outline { ch: 'a', advance: 1255, contours: &[
+        &[(702, 563, 1), (479, 563, 0), (307, 461, 0), (307, 338, 1), (307, 240, 0), (436, 125, 0),
+          (547, 125, 1), (700, 125, 0), (885, 342, 0), (885, 522, 1), (885, 563, 1)],
+        &[(1069, 639, 1), (1069, 0, 1), (885, 0, 1), (885, 170, 1), (822, 68, 0), (634, -29, 0),
+          (498, -29, 1), (326, -29, 0), (123, 164, 0), (123, 326, 1), (123, 515, 0), (376, 707, 0),
+          (627, 707, 1), (885, 707, 1), (885, 725, 1), (885, 852, 0), (718, 991, 0), (567, 991, 1),
+          (471, 991, 0), (289, 945, 0), (205, 899, 1), (205, 1069, 1), (306, 1108, 0), (496, 1147, 0),
+          (586, 1147, 1), (829, 1147, 0), (1069, 895, 0)],
+    ] },
+    Outline { ch: 'b', advance: 1300, contours: &[
+        &[(997, 559, 1), (997, 762, 0), (830, 993, 0), (684, 993, 1), (538, 993, 0), (371, 762, 0),
+          (371, 559, 1), (371, 356, 0), (538, 125, 0), (684, 125, 1), (830, 125, 0), (997, 356, 0)],
+        &[(371, 950, 1), (429, 1050, 0), (606, 1147, 0), (729, 1147, 1), (933, 1147, 0), (1188, 823, 0),
+          (1188, 559, 1), (1188, 295, 0), (933, -29, 0), (729, -29, 1), (606, -29, 0), (429, 68, 0),
+          (371, 168, 1), (371, 0, 1), (186, 0, 1), (186, 1556, 1), (371, 1556, 1)],
+    ] },
+    Outline { ch: 'c', advance: 1126, contours: &[
+        &[(999, 1077, 1), (999, 905, 1), (921, 948, 0), (764, 991, 0), (684, 991, 1), (505, 991, 0),
+          (307, 764, 0), (307, 559, 1), (307, 354, 0), (505, 127, 0), (684, 127, 1), (764, 127, 0),
+          (921, 170, 0), (999, 213, 1), (999, 43, 1), (922, 7, 0), (757, -29, 0), (664, -29, 1),
+          (411, -29, 0), (113, 289, 0), (113, 559, 1), (113, 833, 0), (414, 1147, 0), (676, 1147, 1),
+          (761, 1147, 0), (923, 1112, 0)],
+    ] },
+    Outline { ch: 'd', advance: 1300, contours: &[
+        &[(930, 950, 1), (930, 1556, 1), (1114, 1556, 1), (1114, 0, 1), (930, 0, 1), (930, 168, 1),
+          (872, 68, 0), (695, -29, 0), (571, -29, 1), (368, -29, 0), (113, 295, 0), (113, 559, 1),
+          (113, 823, 0), (368, 1147, 0), (571, 1147, 1), (695, 1147, 0), (872, 1050, 0)],
+        &[(303, 559, 1), (303, 356, 0), (470, 125, 0), (616, 125, 1), (762, 125, 0), (930, 356, 0),
+          (930, 559, 1), (930, 762, 0), (762, 993, 0), (616, 993, 1), (470, 993, 0), (303, 762, 0)],
+    ] },
+    Outline { ch: 'e', advance: 1260, contours: &[
+        &[(1151, 606, 1), (1151, 516, 1), (305, 516, 1), (317, 326, 0), (522, 127, 0), (705, 127, 1),
+          (811, 127, 0), (1010, 179, 0), (1108, 231, 1), (1108, 57, 1), (1009, 15, 0), (801, -29, 0),
+          (694, -29, 1), (426, -29, 0), (113, 283, 0), (113, 549, 1), (113, 824, 0), (410, 1147, 0),
+          (662, 1147, 1), (888, 1147, 0), (1151, 856, 0)],
+        &[(967, 660, 1), (965, 811, 0), (800, 991, 0), (664, 991, 1), (510, 991, 0), (325, 817, 0),
+          (311, 659, 1)],
+    ] },
+    Outline { ch: 'f', advance: 721, contours: &[
+        &[(760, 1556, 1), (760, 1403, 1), (584, 1403, 1), (485, 1403, 0), (408, 1323, 0), (408, 1219, 1),
+          (408, 1120, 1), (711, 1120, 1), (711, 977, 1), (408, 977, 1), (408, 0, 1), (223, 0, 1),
+          (223, 977, 1), (47, 977, 1), (47, 1120, 1), (223, 1120, 1), (223, 1198, 1), (223, 1385, 0),
+          (397, 1556, 0), (586, 1556, 1)],
+    ] },
+    Outline { ch: 'g', advance: 1300, contours: &[
+        &[(930, 573, 1), (930, 773, 0), (765, 993, 0), (616, 993, 1), (468, 993, 0), (303, 773, 0),
+          (303, 573, 1), (303, 374, 0), (468, 154, 0), (616, 154, 1), (765, 154, 0), (930, 374, 0)],
+        &[(1114, 139, 1), (1114, -147, 0), (860, -426, 0), (598, -426, 1), (501, -426, 0), (329, -397, 0),
+          (248, -367, 1), (248, -188, 1), (329, -232, 0), (487, -274, 0), (569, -274, 1), (750, -274, 0),
+          (930, -85, 0), (930, 106, 1), (930, 197, 1), (873, 98, 0), (695, 0, 0), (571, 0, 1), (365, 0, 0),
+          (113, 314, 0), (113, 573, 1), (113, 833, 0), (365, 1147, 0), (571, 1147, 1), (695, 1147, 0),
+          (873, 1049, 0), (930, 950, 1), (930, 1120, 1), (1114, 1120, 1)],
+    ] },
+    Outline { ch: 'h', advance: 1298, contours: &[
+        &[(1124, 676, 1), (1124, 0, 1), (940, 0, 1), (940, 670, 1), (940, 829, 0), (816, 987, 0),
+          (692, 987, 1), (543, 987, 0), (371, 797, 0), (371, 633, 1), (371, 0, 1), (186, 0, 1),
+          (186, 1556, 1), (371, 1556, 1), (371, 946, 1), (437, 1047, 0), (616, 1147, 0), (733, 1147, 1),
+          (926, 1147, 0), (1124, 908, 0)],
+    ] },
+    Outline { ch: 'i', advance: 569, contours: &[
+        &[(193, 1120, 1), (377, 1120, 1), (377, 0, 1), (193, 0, 1)],
+        &[(193, 1556, 1), (377, 1556, 1), (377, 1323, 1), (193, 1323, 1)],
+    ] },
+    Outline { ch: 'j', advance: 569, contours: &[
+        &[(193, 1120, 1), (377, 1120, 1), (377, -20, 1), (377, -234, 0), (214, -426, 0), (33, -426, 1),
+          (-37, -426, 1), (-37, -270, 1), (12, -270, 1), (117, -270, 0), (193, -173, 0), (193, -20, 1)],
+        &[(193, 1556, 1), (377, 1556, 1), (377, 1323, 1), (193, 1323, 1)],
+    ] },
+    Outline { ch: 'k', advance: 1186, contours: &[
+        &[(186, 1556, 1), (371, 1556, 1), (371, 637, 1), (920, 1120, 1), (1155, 1120, 1), (561, 596, 1),
+          (1180, 0, 1), (940, 0, 1), (371, 547, 1), (371, 0, 1), (186, 0, 1)],
+    ] },
+    Outline { ch: 'l', advance: 569, contours: &[
+        &[(193, 1556, 1), (377, 1556, 1), (377, 0, 1), (193, 0, 1)],
+    ] },
+    Outline { ch: 'm', advance: 1995, contours: &[
+        &[(1065, 905, 1), (1134, 1029, 0), (1326, 1147, 0), (1456, 1147, 1), (1631, 1147, 0), (1821, 902, 0),
+          (1821, 676, 1), (1821, 0, 1), (1636, 0, 1), (1636, 670, 1), (1636, 831, 0), (1522, 987, 0),
+          (1405, 987, 1), (1262, 987, 0), (1096, 797, 0), (1096, 633, 1), (1096, 0, 1), (911, 0, 1),
+          (911, 670, 1), (911, 832, 0), (797, 987, 0), (678, 987, 1), (537, 987, 0), (371, 796, 0),
+          (371, 633, 1), (371, 0, 1), (186, 0, 1), (186, 1120, 1), (371, 1120, 1), (371, 946, 1),
+          (434, 1049, 0), (610, 1147, 0), (731, 1147, 1), (853, 1147, 0), (1024, 1023, 0)],
+    ] },
+    Outline { ch: 'n', advance: 1298, contours: &[
+        &[(1124, 676, 1), (1124, 0, 1), (940, 0, 1), (940, 670, 1), (940, 829, 0), (816, 987, 0),
+          (692, 987, 1), (543, 987, 0), (371, 797, 0), (371, 633, 1), (371, 0, 1), (186, 0, 1),
+          (186, 1120, 1), (371, 1120, 1), (371, 946, 1), (437, 1047, 0), (616, 1147, 0), (733, 1147, 1),
+          (926, 1147, 0), (1124, 908, 0)],
+    ] },
+    Outline { ch: 'o', advance: 1253, contours: &[
+        &[(627, 991, 1), (479, 991, 0), (307, 760, 0), (307, 559, 1), (307, 358, 0), (478, 127, 0),
+          (627, 127, 1), (774, 127, 0), (946, 359, 0), (946, 559, 1), (946, 758, 0), (774, 991, 0)],
+        &[(627, 1147, 1), (867, 1147, 0), (1141, 835, 0), (1141, 559, 1), (1141, 284, 0), (867, -29, 0),
+          (627, -29, 1), (386, -29, 0), (113, 284, 0), (113, 559, 1), (113, 835, 0), (386, 1147, 0)],
+    ] },
+    Outline { ch: 'p', advance: 1300, contours: &[
+        &[(371, 168, 1), (371, -426, 1), (186, -426, 1), (186, 1120, 1), (371, 1120, 1), (371, 950, 1),
+          (429, 1050, 0), (606, 1147, 0), (729, 1147, 1), (933, 1147, 0), (1188, 823, 0), (1188, 559, 1),
+          (1188, 295, 0), (933, -29, 0), (729, -29, 1), (606, -29, 0), (429, 68, 0)],
+        &[(997, 559, 1), (997, 762, 0), (830, 993, 0), (684, 993, 1), (538, 993, 0), (371, 762, 0),
+          (371, 559, 1), (371, 356, 0), (538, 125, 0), (684, 125, 1), (830, 125, 0), (997, 356, 0)],
+    ] },
+    Outline { ch: 'q', advance: 1300, contours: &[
+        &[(303, 559, 1), (303, 356, 0), (470, 125, 0), (616, 125, 1), (762, 125, 0), (930, 356, 0),
+          (930, 559, 1), (930, 762, 0), (762, 993, 0), (616, 993, 1), (470, 993, 0), (303, 762, 0)],
+        &[(930, 168, 1), (872, 68, 0), (695, -29, 0), (571, -29, 1), (368, -29, 0), (113, 295, 0),
+          (113, 559, 1), (113, 823, 0), (368, 1147, 0), (571, 1147, 1), (695, 1147, 0), (872, 1050, 0),
+          (930, 950, 1), (930, 1120, 1), (1114, 1120, 1), (1114, -426, 1), (930, -426, 1)],
+    ] },
+    Outline { ch: 'r', advance: 842, contours: &[
+        &[(842, 948, 1), (811, 966, 0), (738, 983, 0), (694, 983, 1), (538, 983, 0), (371, 780, 0),
+          (371, 590, 1), (371, 0, 1), (186, 0, 1), (186, 1120, 1), (371, 1120, 1), (371, 946, 1),
+          (429, 1048, 0), (615, 1147, 0), (748, 1147, 1), (767, 1147, 0), (813, 1142, 0), (841, 1137, 1)],
+    ] },
+    Outline { ch: 's', advance: 1067, contours: &[
+        &[(907, 1087, 1), (907, 913, 1), (829, 953, 0), (661, 993, 0), (571, 993, 1), (434, 993, 0),
+          (297, 909, 0), (297, 825, 1), (297, 761, 0), (395, 688, 0), (543, 655, 1), (606, 641, 1),
+          (802, 599, 0), (967, 446, 0), (967, 309, 1), (967, 153, 0), (720, -29, 0), (504, -29, 1),
+          (414, -29, 0), (219, 6, 0), (111, 41, 1), (111, 231, 1), (213, 178, 0), (411, 125, 0),
+          (508, 125, 1), (638, 125, 0), (778, 214, 0), (778, 295, 1), (778, 370, 0), (677, 450, 0),
+          (506, 487, 1), (442, 502, 1), (271, 538, 0), (119, 687, 0), (119, 817, 1), (119, 975, 0),
+          (343, 1147, 0), (549, 1147, 1), (651, 1147, 0), (831, 1117, 0)],
+    ] },
+    Outline { ch: 't', advance: 803, contours: &[
+        &[(375, 1438, 1), (375, 1120, 1), (754, 1120, 1), (754, 977, 1), (375, 977, 1), (375, 369, 1),
+          (375, 232, 0), (450, 154, 0), (565, 154, 1), (754, 154, 1), (754, 0, 1), (565, 0, 1), (352, 0, 0),
+          (190, 159, 0), (190, 369, 1), (190, 977, 1), (55, 977, 1), (55, 1120, 1), (190, 1120, 1),
+          (190, 1438, 1)],
+    ] },
+    Outline { ch: 'u', advance: 1298, contours: &[
+        &[(174, 442, 1), (174, 1120, 1), (358, 1120, 1), (358, 449, 1), (358, 290, 0), (482, 131, 0),
+          (606, 131, 1), (755, 131, 0), (928, 321, 0), (928, 485, 1), (928, 1120, 1), (1112, 1120, 1),
+          (1112, 0, 1), (928, 0, 1), (928, 172, 1), (861, 70, 0), (684, -29, 0), (567, -29, 1),
+          (374, -29, 0), (174, 211, 0)],
+        &[(637, 1147, 1)],
+    ] },
+    Outline { ch: 'v', advance: 1212, contours: &[
+        &[(61, 1120, 1), (256, 1120, 1), (606, 180, 1), (956, 1120, 1), (1151, 1120, 1), (731, 0, 1),
+          (481, 0, 1)],
+    ] },
+    Outline { ch: 'w', advance: 1675, contours: &[
+        &[(86, 1120, 1), (270, 1120, 1), (500, 246, 1), (729, 1120, 1), (946, 1120, 1), (1176, 246, 1),
+          (1405, 1120, 1), (1589, 1120, 1), (1296, 0, 1), (1079, 0, 1), (838, 918, 1), (596, 0, 1),
+          (379, 0, 1)],
+    ] },
+    Outline { ch: 'x', advance: 1212, contours: &[
+        &[(1124, 1120, 1), (719, 575, 1), (1145, 0, 1), (928, 0, 1), (602, 440, 1), (276, 0, 1), (59, 0, 1),
+          (494, 586, 1), (96, 1120, 1), (313, 1120, 1), (610, 721, 1), (907, 1120, 1)],
+    ] },
+    Outline { ch: 'y', advance: 1212, contours: &[
+        &[(659, -104, 1), (581, -304, 0), (433, -426, 0), (309, -426, 1), (162, -426, 1), (162, -272, 1),
+          (270, -272, 1), (346, -272, 0), (430, -200, 0), (481, -66, 1), (514, 18, 1), (61, 1120, 1),
+          (256, 1120, 1), (606, 244, 1), (956, 1120, 1), (1151, 1120, 1)],
+    ] },
+    Outline { ch: 'z', advance: 1075, contours: &[
+        &[(113, 1120, 1), (987, 1120, 1), (987, 952, 1), (295, 147, 1), (987, 147, 1), (987, 0, 1),
+          (88, 0, 1), (88, 168, 1), (780, 973, 1), (113, 973, 1)],
+    ] },
+    Outline { ch: '-', advance: 739, contours: &[
+        &[(100, 643, 1), (639, 643, 1), (639, 479, 1), (100, 479, 1)],
+    ] },
+    Outline { ch: '+', advance: 1716, contours: &[
+        &[(942, 1284, 1), (942, 727, 1), (1499, 727, 1), (1499, 557, 1), (942, 557, 1), (942, 0, 1),
+          (774, 0, 1), (774, 557, 1), (217, 557, 1), (217, 727, 1), (774, 727, 1), (774, 1284, 1)],
+    ] },
+    Outline { ch: '.', advance: 651, contours: &[
+        &[(219, 254, 1), (430, 254, 1), (430, 0, 1), (219, 0, 1)],
+    ] },
+    Outline { ch: ',', advance: 651, contours: &[
+        &[(240, 254, 1), (451, 254, 1), (451, 82, 1), (287, -238, 1), (158, -238, 1), (240, 82, 1)],
+    ] },
+    Outline { ch: '/', advance: 690, contours: &[
+        &[(520, 1493, 1), (690, 1493, 1), (170, -190, 1), (0, -190, 1)],
+    ] },
+    Outline { ch: '(', advance: 799, contours: &[
+        &[(635, 1554, 1), (501, 1324, 0), (371, 874, 0), (371, 643, 1), (371, 412, 0), (502, -41, 0),
+          (635, -270, 1), (475, -270, 1), (325, -35, 0), (176, 419, 0), (176, 643, 1), (176, 866, 0),
+          (324, 1318, 0), (475, 1554, 1)],
+    ] },
+    Outline { ch: ')', advance: 799, contours: &[
+        &[(164, 1554, 1), (324, 1554, 1), (474, 1318, 0), (623, 866, 0), (623, 643, 1), (623, 419, 0),
+          (474, -35, 0), (324, -270, 1), (164, -270, 1), (297, -41, 0), (428, 412, 0), (428, 643, 1),
+          (428, 874, 0), (297, 1324, 0)],
+    ] },
+    Outline { ch: '%', advance: 1946, contours: &[
+        &[(1489, 657, 1), (1402, 657, 0), (1303, 509, 0), (1303, 377, 1), (1303, 247, 0), (1402, 98, 0),
+          (1489, 98, 1), (1574, 98, 0), (1673, 247, 0), (1673, 377, 1), (1673, 508, 0), (1574, 657, 0)],
+        &[(1489, 784, 1), (1647, 784, 0), (1833, 564, 0), (1833, 377, 1), (1833, 190, 0), (1646, -29, 0),
+          (1489, -29, 1), (1329, -29, 0), (1143, 190, 0), (1143, 377, 1), (1143, 565, 0), (1330, 784, 0)],
+        &[(457, 1393, 1), (371, 1393, 0), (272, 1244, 0), (272, 1114, 1), (272, 982, 0), (370, 834, 0),
+          (457, 834, 1), (544, 834, 0), (643, 982, 0), (643, 1114, 1), (643, 1243, 0), (543, 1393, 0)],
+        &[(1360, 1520, 1), (1520, 1520, 1), (586, -29, 1), (426, -29, 1)],
+        &[(457, 1520, 1), (615, 1520, 0), (803, 1301, 0), (803, 1114, 1), (803, 925, 0), (616, 707, 0),
+          (457, 707, 1), (298, 707, 0), (113, 926, 0), (113, 1114, 1), (113, 1300, 0), (299, 1520, 0)],
+    ] },
+    Outline { ch: ':', advance: 690, contours: &[
+        &[(240, 254, 1), (451, 254, 1), (451, 0, 1), (240, 0, 1)],
+        &[(240, 1059, 1), (451, 1059, 1), (451, 805, 1), (240, 805, 1)],
+    ] },
+    Outline { ch: '\'', advance: 563, contours: &[
+        &[(367, 1493, 1), (367, 938, 1), (197, 938, 1), (197, 1493, 1)],
+    ] },
+    Outline { ch: '&', advance: 1597, contours: &[
+        &[(498, 803, 1), (407, 722, 0), (322, 561, 0), (322, 473, 1), (322, 327, 0), (534, 133, 0),
+          (694, 133, 1), (789, 133, 0), (955, 196, 0), (1028, 260, 1)],
+        &[(639, 915, 1), (1147, 395, 1), (1206, 484, 0), (1272, 687, 0), (1278, 801, 1), (1464, 801, 1),
+          (1452, 669, 0), (1348, 411, 0), (1255, 285, 1), (1534, 0, 1), (1282, 0, 1), (1139, 147, 1),
+          (1035, 58, 0), (807, -29, 0), (676, -29, 1), (435, -29, 0), (129, 246, 0), (129, 461, 1),
+          (129, 589, 0), (263, 814, 0), (397, 913, 1), (349, 976, 0), (299, 1101, 0), (299, 1161, 1),
+          (299, 1323, 0), (521, 1520, 0), (705, 1520, 1), (788, 1520, 0), (953, 1484, 0), (1038, 1448, 1),
+          (1038, 1266, 1), (951, 1313, 0), (793, 1362, 0), (725, 1362, 1), (620, 1362, 0), (489, 1251, 0),
+          (489, 1163, 1), (489, 1112, 0), (548, 1009, 0)],
+    ] },
+];
@@ -0,0 +1,162 @@
+//! Mapping of recognized header text to OpenPowerlifting column names.
+//!
+//! Meet results label their columns in many ways: `BWt (Kg)`, `Bodyweight`
+//! and `BW` all mean `BodyweightKg`. Headers are compared after dropping
+//! case, spaces and punctuation, so small differences in recognition and
+//! layout still match.
+
+use std::collections::HashMap;
+
+/// The number of rows at the top of a table that may hold the header row.
+/// Title rows usually come before it.
+const MAX_HEADER_ROW: usize = 5;
+
+/// The number of cells that must map for a row to be taken as the header.
+const MIN_HEADER_MATCHES: usize = 2;
+
+/// Synonyms for each OpenPowerlifting column, before normalization.
+const OPENPOWERLIFTING: &[(&str, &[&str])] = &[
+    ("Place", &["Pl", "Plc", "Rank", "Pos", "Position", "#"]),
+    ("Name", &["Lifter", "Lifter Name", "Athlete", "Competitor"]),
+    ("Sex", &["Gender", "M/F"]),
+    ("Age", &[]),
+    ("Division", &["Div", "Category", "Age Class"]),
+    ("Equipment", &["Equip", "Eq"]),
+    ("WeightClassKg", &["Weight Class", "WtCls", "WtCl", "WC", "Class", "Cls", "Wt Class"]),
+    ("BodyweightKg", &["Bodyweight", "Body Weight", "BWt", "BW", "Weight", "Wt"]),
+    ("Squat1Kg", &["Squat 1", "SQ1", "S1"]),
+    ("Squat2Kg", &["Squat 2", "SQ2", "S2"]),
+    ("Squat3Kg", &["Squat 3", "SQ3", "S3"]),
+    ("Best3SquatKg", &["Best Squat", "Squat", "SQ", "Best SQ"]),
+    ("Bench1Kg", &["Bench 1", "BP1", "B1"]),
+    ("Bench2Kg", &["Bench 2", "BP2", "B2"]),
+    ("Bench3Kg", &["Bench 3", "BP3", "B3"]),
+    ("Best3BenchKg", &["Best Bench", "Bench", "BP", "Best BP"]),
+    ("Deadlift1Kg", &["Deadlift 1", "DL1", "D1"]),
+    ("Deadlift2Kg", &["Deadlift 2", "DL2", "D2"]),
+    ("Deadlift3Kg", &["Deadlift 3", "DL3", "D3"]),
+    ("Best3DeadliftKg", &["Best Deadlift", "Deadlift", "DL", "Best DL"]),
+    ("TotalKg", &["Total", "PL Total", "Tot"]),
+    ("Wilks", &[]),
+    ("Glossbrenner", &["Gloss"]),
+    ("Team", &["Club"]),
+    ("State", &[]),
+    ("Country", &["Nation"]),
+    ("Event", &["Events"]),
+];
+
+
+/// A dictionary from header text to canonical column names.
+#[derive(Clone, Debug, Default)]
+pub struct Schema {
+    /// Canonical names, indexed by normalized synonym.
+    names: HashMap<String, String>,
+}
+
+impl Schema {
+    /// A schema with no columns at all.
+    pub fn new() -> Schema {
+        Schema::default()
+    }
+
+    /// The columns of OpenPowerlifting's `entries.csv`, with common synonyms.
+    pub fn openpowerlifting() -> Schema {
+        let mut schema = Schema::new();
+        for &(canonical, synonyms) in OPENPOWERLIFTING {
+            schema.add(canonical, canonical);
+            for synonym in synonyms {
+                schema.add(canonical, synonym);
+            }
+        }
+        schema
+    }
+
+    /// Maps `synonym` to `canonical`, replacing any earlier mapping.
+    pub fn add(&mut self, canonical: &str, synonym: &str) {
+        self.names.insert(normalize(synonym), canonical.to_string());
+    }
+
+    /// Adds synonyms from text with one column per line, as in
+    /// `BodyweightKg = BWt, Body Wt`. Blank lines and lines
+    /// starting with `#` are skipped.
+    pub fn extend_from_str(&mut self, text: &str) -> Result<(), &'static str> {
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.splitn(2, '=');
+            let canonical = parts.next().unwrap_or("").trim();
+            let synonyms = match parts.next() {
+                Some(synonyms) if !canonical.is_empty() => synonyms,
+                _ => return Err("Synonym lines must look like `Canonical = synonym, synonym`."),
+            };
+
+            self.add(canonical, canonical);
+            for synonym in synonyms.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+                self.add(canonical, synonym);
+            }
+        }
+        Ok(())
+    }
+
+    /// The canonical name for a header, if it is known.
+    /// A trailing unit such as `(Kg)` is ignored if need be.
+    pub fn canonical(&self, header: &str) -> Option<&str> {
+        let key = normalize(header);
+        if key.is_empty() {
+            return None;
+        }
+
+        self.names.get(&key)
+            .or_else(|| ["kgs", "kg"].iter()
+                .filter(|unit| key.len() > unit.len() && key.ends_with(*unit))
+                .filter_map(|unit| self.names.get(&key[.. key.len() - unit.len()]))
+                .next())
+            .map(|name| name.as_str())
+    }
+
+    /// The index of the header row near the top of the table: the row
+    /// with the most known headers, if it has enough of them.
+    pub fn header_row(&self, rows: &[Vec<String>]) -> Option<usize> {
+        let matches = |row: &Vec<String>| row.iter().filter(|h| self.canonical(h).is_some()).count();
+        rows.iter().take(MAX_HEADER_ROW).enumerate()
+            .map(|(i, row)| (matches(row), i))
+            .max_by_key(|&(count, i)| (count, -(i as i64)))
+            .filter(|&(count, _)| count >= MIN_HEADER_MATCHES)
+            .map(|(_, i)| i)
+    }
+
+    /// Finds the header row near the top of the table and renames its cells
+    /// to their canonical names. Headers that are not known keep their text,
+    /// and title rows above the header are kept as they are. The rows are
+    /// returned unchanged if no row looks like a header.
+    pub fn map_rows(&self, mut rows: Vec<Vec<String>>) -> Vec<Vec<String>> {
+        if let Some(header) = self.header_row(&rows) {
+            for h in &mut rows[header] {
+                if let Some(name) = self.canonical(h) {
+                    *h = name.to_string();
+                }
+            }
+        }
+        rows
+    }
+}
+
+
+/// Whether the canonical name is that of a single attempt, as `Bench2Kg` is.
+pub fn is_attempt(canonical: &str) -> bool {
+    ["Squat", "Bench", "Deadlift"].iter().any(|lift| {
+        canonical.starts_with(lift) && canonical.ends_with("Kg")
+            && canonical[lift.len() .. canonical.len() - 2].parse::<u32>().is_ok()
+    })
+}
+
+
+/// Lowercases the text and removes everything but letters, digits and `#`.
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric() || *c == '#')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
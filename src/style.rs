@@ -0,0 +1,159 @@
+//! The styling of a cell, as read from its color crop.
+//!
+//! Federations mark failed attempts in many ways: red text, a red or pink
+//! fill, a line struck through the weight, or an X over it. Recognition
+//! works on a gray crop, where none of these can be told apart, so the
+//! styling is read from the colors of the cell instead.
+
+use image::{DynamicImage, GenericImage, Rgba};
+
+use inset;
+
+/// How much brighter the red channel must be than the others
+/// for a color to be red. Pink fills just pass.
+const MIN_REDNESS: i32 = 30;
+
+/// How far in luma a pixel must be from the background to be ink.
+const MIN_INK_CONTRAST: i32 = 64;
+
+/// The fraction of ink that must be red for the text to be red.
+const MIN_RED_INK: f32 = 0.5;
+
+/// Text shorter than this is too small to tell lines through it
+/// from its own strokes.
+const MIN_TEXT_HEIGHT_PX: u32 = 8;
+
+/// The fraction of a row or diagonal of the text that a line
+/// through it must cover without a break.
+const MIN_LINE_COVERAGE: f32 = 0.9;
+
+
+/// The styling found in one cell.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CellStyle {
+    /// Most of the text is red.
+    pub red_text: bool,
+    /// The background is red or pink.
+    pub red_fill: bool,
+    /// A horizontal line runs through the middle of the text.
+    pub struck_through: bool,
+    /// Lines run from corner to corner of the text both ways.
+    pub crossed_out: bool,
+}
+
+impl CellStyle {
+    /// Whether the styling marks the cell as a failed attempt.
+    pub fn is_failed(&self) -> bool {
+        self.red_text || self.red_fill || self.struck_through || self.crossed_out
+    }
+}
+
+
+/// Reads the styling of a cell from its crop, in its original colors.
+pub fn analyze(crop: &DynamicImage) -> CellStyle {
+    let (width, height) = crop.dimensions();
+    let (inset_x, inset_y) = inset(width, height);
+    let (width, height) = (width - 2 * inset_x, height - 2 * inset_y);
+    if width == 0 || height == 0 {
+        return CellStyle::default();
+    }
+
+    let pixels: Vec<Rgba<u8>> = (0 .. height)
+        .flat_map(|y| (0 .. width).map(move |x| (x, y)))
+        .map(|(x, y)| crop.get_pixel(x + inset_x, y + inset_y))
+        .collect();
+
+    // Most of a cell is background, so its color is the median of each channel.
+    let mut background = [0; 3];
+    for (channel, value) in background.iter_mut().enumerate() {
+        let mut values: Vec<u8> = pixels.iter().map(|px| px.data[channel]).collect();
+        values.sort();
+        *value = values[values.len() / 2];
+    }
+    let background = Rgba([background[0], background[1], background[2], 255]);
+
+    let ink: Vec<bool> = pixels.iter()
+        .map(|px| (luma(px) - luma(&background)).abs() >= MIN_INK_CONTRAST)
+        .collect();
+    let ink_count = ink.iter().filter(|&&ink| ink).count();
+    let red_ink = pixels.iter().zip(&ink)
+        .filter(|&(px, &ink)| ink && redness(px) - i32::max(redness(&background), 0) >= MIN_REDNESS)
+        .count();
+
+    let mut style = CellStyle {
+        red_text: ink_count > 0 && red_ink as f32 >= MIN_RED_INK * ink_count as f32,
+        red_fill: redness(&background) >= MIN_REDNESS && luma(&background) >= 128,
+        struck_through: false,
+        crossed_out: false,
+    };
+
+    // Lines are only looked for over text wider than tall. Weights have
+    // several digits, while single glyphs such as `4` have bars of their own.
+    if let Some((left, top, right, bottom)) = extent(&ink, width, height) {
+        let (w, h) = (right - left, bottom - top);
+        if h + 1 < MIN_TEXT_HEIGHT_PX || w < h {
+            return style;
+        }
+
+        let at = |x: u32, y: u32| ink[(y * width + x) as usize];
+        let covers = |run: usize| run as f32 >= MIN_LINE_COVERAGE * (w + 1) as f32;
+
+        // The line must run through the middle third of the text, unlike
+        // the bars of glyphs on a line of their own in two-line text.
+        style.struck_through = (top + h / 3 ..= bottom - h / 3)
+            .any(|y| covers(longest_run((left ..= right).map(|x| at(x, y)))));
+
+        // A diagonal is followed one pixel above and below, as it runs in
+        // steps, but not sideways, so the gaps between glyphs still count.
+        let near = |x: u32, y: u32| {
+            (y.saturating_sub(1) ..= u32::min(y + 1, height - 1)).any(|y| at(x, y))
+        };
+        let diagonal = |flip: bool| (0 ..= w).map(move |i| {
+            let y = if flip { bottom - i * h / w } else { top + i * h / w };
+            near(left + i, y)
+        });
+        style.crossed_out = covers(longest_run(diagonal(false))) && covers(longest_run(diagonal(true)));
+    }
+
+    style
+}
+
+
+/// The length of the longest unbroken run of hits.
+fn longest_run<I: Iterator<Item = bool>>(hits: I) -> usize {
+    let (mut longest, mut run) = (0, 0);
+    for hit in hits {
+        run = if hit { run + 1 } else { 0 };
+        longest = usize::max(longest, run);
+    }
+    longest
+}
+
+
+/// The bounding box of the ink, as (left, top, right, bottom), inclusive.
+fn extent(ink: &[bool], width: u32, height: u32) -> Option<(u32, u32, u32, u32)> {
+    let mut found = None;
+    for y in 0 .. height {
+        for x in 0 .. width {
+            if ink[(y * width + x) as usize] {
+                let (left, top, right, bottom) = found.unwrap_or((x, y, x, y));
+                found = Some((u32::min(left, x), top, u32::max(right, x), u32::max(bottom, y)));
+            }
+        }
+    }
+    found
+}
+
+
+/// The brightness of a color, from 0 to 255.
+fn luma(px: &Rgba<u8>) -> i32 {
+    let [r, g, b, _] = px.data;
+    (299 * r as i32 + 587 * g as i32 + 114 * b as i32) / 1000
+}
+
+
+/// How much the red channel stands out over green and blue.
+fn redness(px: &Rgba<u8>) -> i32 {
+    let [r, g, b, _] = px.data;
+    r as i32 - i32::max(g as i32, b as i32)
+}
@@ -0,0 +1,750 @@
+//! Synthetic spreadsheet screenshots with known contents.
+//!
+//! Each render is a random table of meet results drawn with the built-in
+//! bitmap `font` or the `sans` typeface, along with the exact rectangle of
+//! every cell and the CSV that a perfect conversion would produce.
+
+use image::{DynamicImage, ImageBuffer, Rgba, RgbaImage};
+
+use csv;
+use font;
+use sans;
+
+use std::io::{self, Write};
+
+
+/// A small, seedable pseudo-random number generator (xorshift64*).
+///
+/// Renders must be reproducible from a seed across platforms and
+/// crate versions, so this does not depend on an external crate.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // Zero is a fixed point of xorshift.
+        Rng(seed ^ 0x9E37_79B9_7F4A_7C15)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// A uniformly distributed integer in `lo ..= hi`.
+    pub fn range(&mut self, lo: u32, hi: u32) -> u32 {
+        lo + (self.next_u64() % (hi as u64 - lo as u64 + 1)) as u32
+    }
+
+    /// A uniformly distributed float in `[0, 1)`.
+    pub fn unit(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Returns true with probability `p`.
+    pub fn chance(&mut self, p: f32) -> bool {
+        self.unit() < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0, items.len() as u32 - 1) as usize]
+    }
+}
+
+
+/// How a synthetic table is drawn.
+#[derive(Clone, Debug)]
+pub struct Style {
+    /// The typeface text is drawn in.
+    pub font: Font,
+    /// Screen pixels per font pixel of the bitmap font.
+    pub font_scale: u32,
+    /// Thickens every glyph stroke by one screen pixel.
+    pub bold: bool,
+    /// Space between glyphs of the bitmap font, in font pixels.
+    pub letter_spacing: u32,
+    /// Space between the text and the cell border, in screen pixels.
+    pub padding: u32,
+    /// Fixed interior width of each column. Columns past the end of the
+    /// list, or all columns if empty, are sized to fit their contents.
+    pub col_widths: Vec<u32>,
+    /// Thickness of gridlines, in screen pixels. Zero draws no gridlines.
+    pub line_width: u32,
+    pub line_color: Rgba<u8>,
+    pub text_color: Rgba<u8>,
+    pub background: Rgba<u8>,
+    /// Fill for every other data row, if rows are zebra-striped.
+    pub row_shading: Option<Rgba<u8>>,
+    /// Fill for the header row, if it is highlighted. The header is then
+    /// lettered and ruled in the background color.
+    pub header_fill: Option<Rgba<u8>>,
+    /// Number of merged title rows above the header row.
+    pub title_rows: u32,
+    /// Blank space around the table, in screen pixels.
+    pub margin: u32,
+    /// Shows the table in a spreadsheet window instead of on its own,
+    /// with a toolbar, column letters, row numbers, empty cells past the
+    /// data and a scrollbar. None of them are in the ground truth.
+    pub spreadsheet: bool,
+    /// How failed attempts are shown. Their weights are negative in
+    /// the ground truth however they are shown.
+    pub failed_mark: FailedMark,
+}
+
+impl Default for Style {
+    fn default() -> Style {
+        Style {
+            font: Font::Bitmap,
+            font_scale: 2,
+            bold: false,
+            letter_spacing: 1,
+            padding: 4,
+            col_widths: Vec::new(),
+            line_width: 1,
+            line_color: Rgba([0, 0, 0, 255]),
+            text_color: Rgba([0, 0, 0, 255]),
+            background: Rgba([255, 255, 255, 255]),
+            row_shading: None,
+            header_fill: None,
+            title_rows: 1,
+            margin: 0,
+            spreadsheet: false,
+            failed_mark: FailedMark::Minus,
+        }
+    }
+}
+
+impl Style {
+    /// Picks a random, plausible style.
+    pub fn random(rng: &mut Rng) -> Style {
+        let gray = rng.range(0, 200) as u8;
+        let line_color = match rng.range(0, 3) {
+            0 => Rgba([0, 0, 0, 255]),
+            1 => Rgba([gray, gray, gray, 255]),
+            2 => Rgba([rng.range(0, 120) as u8, rng.range(60, 160) as u8, rng.range(150, 255) as u8, 255]),
+            _ => Rgba([rng.range(0, 255) as u8, rng.range(0, 255) as u8, rng.range(0, 255) as u8, 255]),
+        };
+
+        let row_shading = if rng.chance(0.4) {
+            let shade = rng.range(200, 245) as u8;
+            Some(Rgba([shade, shade, rng.range(shade as u32, 255) as u8, 255]))
+        } else {
+            None
+        };
+
+        Style {
+            font: if rng.chance(0.3) { Font::Sans(rng.range(11, 20) as f32) } else { Font::Bitmap },
+            font_scale: rng.range(1, 3),
+            bold: rng.chance(0.3),
+            letter_spacing: rng.range(1, 2),
+            padding: rng.range(2, 8),
+            col_widths: Vec::new(),
+            line_width: if rng.chance(0.15) { 0 } else { rng.range(1, 2) },
+            line_color,
+            text_color: Rgba([rng.range(0, 40) as u8, rng.range(0, 40) as u8, rng.range(0, 40) as u8, 255]),
+            background: Rgba([255, 255, 255, 255]),
+            row_shading,
+            title_rows: rng.range(0, 2),
+            margin: rng.range(0, 16),
+            header_fill: if rng.chance(0.25) {
+                Some(Rgba([rng.range(0, 40) as u8, rng.range(0, 40) as u8, rng.range(0, 120) as u8, 255]))
+            } else {
+                None
+            },
+            spreadsheet: false,
+            failed_mark: FailedMark::Minus,
+        }
+    }
+
+    /// The width of `text` when drawn, in screen pixels.
+    pub fn text_width(&self, text: &str) -> u32 {
+        let n = text.chars().count() as u32;
+        if n == 0 {
+            return 0;
+        }
+        let bold = if self.bold { 1 } else { 0 };
+        match self.font {
+            Font::Bitmap => {
+                let glyphs = n * font::GLYPH_WIDTH + (n - 1) * self.letter_spacing;
+                glyphs * self.font_scale + bold
+            }
+            Font::Sans(size) => sans::text_width(text, size) + bold,
+        }
+    }
+
+    /// The height of a line of text, in screen pixels.
+    pub fn text_height(&self) -> u32 {
+        match self.font {
+            Font::Bitmap => font::GLYPH_HEIGHT * self.font_scale,
+            Font::Sans(size) => sans::line_height(size),
+        }
+    }
+
+    /// The thickness of a stroke of the pen, in screen pixels.
+    fn stroke_width(&self) -> u32 {
+        match self.font {
+            Font::Bitmap => self.font_scale,
+            Font::Sans(size) => u32::max((size * SANS_STROKE_EM).round() as u32, 1),
+        }
+    }
+}
+
+
+/// The typeface text is drawn in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Font {
+    /// The bitmap `font`, scaled by `font_scale` and spaced by `letter_spacing`.
+    Bitmap,
+    /// The `sans` typeface, anti-aliased, at this many pixels to the em.
+    Sans(f32),
+}
+
+/// The thickness of a stroke of `sans`, in ems.
+const SANS_STROKE_EM: f32 = 0.09;
+
+
+/// How a failed attempt is shown in a rendered table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FailedMark {
+    /// A minus sign before the weight, as OpenPowerlifting writes it.
+    Minus,
+    RedText,
+    /// A pink fill, as conditional formatting gives.
+    RedFill,
+    /// A line through the middle of the weight.
+    Strikethrough,
+    /// Lines from corner to corner of the weight, both ways.
+    Cross,
+}
+
+
+/// The ground truth for one cell of a synthetic table.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TruthCell {
+    pub row: u32,
+    pub col: u32,
+    /// Number of grid columns covered by this cell.
+    pub col_span: u32,
+
+    /// The interior of the cell, excluding gridlines.
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+
+    pub text: String,
+}
+
+
+/// A rendered table together with its ground truth.
+pub struct Synthetic {
+    pub image: DynamicImage,
+    /// Every cell, sorted by (row, col).
+    pub cells: Vec<TruthCell>,
+    /// Expected CSV contents, indexed by [row][col]. Merged cells put
+    /// their text in their first column and leave the rest blank.
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Synthetic {
+    /// Writes the expected CSV.
+    pub fn write_csv<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        csv::write_rows(w, &self.rows)
+    }
+
+    /// Writes the geometry of every cell as CSV.
+    pub fn write_geometry<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        writeln!(w, "row,col,col_span,x,y,width,height")?;
+        for cell in &self.cells {
+            writeln!(w, "{},{},{},{},{},{},{}", cell.row, cell.col, cell.col_span,
+                   cell.x, cell.y, cell.width, cell.height)?;
+        }
+        Ok(())
+    }
+}
+
+
+const HEADERS: &[&str] = &[
+    "Place", "Name", "Sex", "Div", "WtCls", "BWt",
+    "Squat 1", "Squat 2", "Squat 3", "Best Squat",
+    "Bench 1", "Bench 2", "Bench 3", "Best Bench",
+    "Deadlift 1", "Deadlift 2", "Deadlift 3", "Best Deadlift",
+    "Total",
+];
+
+const FIRST_NAMES: &[&str] = &[
+    "Lauren", "Annie", "Les", "Leino", "Steven", "Jesse", "Rob", "Mark",
+    "Paul", "Eddie", "Jacob", "John", "Maria", "Kim", "Sarah", "Tom",
+];
+
+const LAST_NAMES: &[&str] = &[
+    "Black", "Schubert", "Winters", "Ahlstedt", "Stapley", "Bowerman",
+    "Simpson", "Kohberg-Ostrasz", "Faichney", "Longford", "Harrison",
+    "Hillier", "O'Neil", "Nguyen", "Smith", "McDonald",
+];
+
+const DIVISIONS: &[&str] = &["Open", "Junior", "Sub-Junior", "M1", "M2", "M3", "Teen 2"];
+
+const MEN_CLASSES: &[&str] = &["59", "66", "74", "83", "93", "105", "120", "120+"];
+
+const WOMEN_CLASSES: &[&str] = &["47", "52", "57", "63", "72", "84", "84+"];
+
+const MEET_NAMES: &[&str] = &[
+    "GPC Winter Classic", "Spring Open", "State Championships",
+    "Raw Nationals", "Summer Showdown", "Novice Cup",
+];
+
+
+/// Formats a weight in kilograms the way spreadsheets usually show it.
+fn format_kg(kg: f32) -> String {
+    if kg.fract() == 0.0 {
+        format!("{}", kg)
+    } else {
+        format!("{:.1}", kg)
+    }
+}
+
+
+/// Generates three attempts near `base`. Failed attempts are negative.
+fn attempts(rng: &mut Rng, base: f32) -> (Vec<String>, f32) {
+    let mut weight = (base / 2.5).round() * 2.5;
+    let mut best: f32 = 0.0;
+    let mut out = Vec::new();
+
+    for _ in 0 .. 3 {
+        if rng.chance(0.25) {
+            out.push(format_kg(-weight));
+        } else {
+            best = best.max(weight);
+            out.push(format_kg(weight));
+            weight += 2.5 * rng.range(1, 6) as f32;
+        }
+    }
+
+    (out, best)
+}
+
+
+/// Produces the header row and random lifter rows.
+fn random_results(rng: &mut Rng, lifters: u32) -> Vec<Vec<String>> {
+    let mut entries: Vec<(f32, Vec<String>)> = Vec::new();
+
+    for _ in 0 .. lifters {
+        let male = rng.chance(0.6);
+        let name = format!("{} {}", rng.choose(FIRST_NAMES), rng.choose(LAST_NAMES));
+        let class = if male { rng.choose(MEN_CLASSES) } else { rng.choose(WOMEN_CLASSES) };
+        let bodyweight = 45.0 + rng.unit() * 80.0;
+        let strength = (if male { 1.0 } else { 0.6 }) * (0.8 + rng.unit());
+
+        let (squats, squat) = attempts(rng, 180.0 * strength);
+        let (benches, bench) = attempts(rng, 120.0 * strength);
+        let (deadlifts, deadlift) = attempts(rng, 210.0 * strength);
+        let bombed = squat == 0.0 || bench == 0.0 || deadlift == 0.0;
+        let total = if bombed { 0.0 } else { squat + bench + deadlift };
+
+        let best = |kg: f32| if kg == 0.0 { String::new() } else { format_kg(kg) };
+
+        let mut row = vec![
+            String::new(),
+            name,
+            if male { "M" } else { "F" }.to_string(),
+            rng.choose(DIVISIONS).to_string(),
+            class.to_string(),
+            format!("{:.2}", bodyweight),
+        ];
+        row.extend(squats);
+        row.push(best(squat));
+        row.extend(benches);
+        row.push(best(bench));
+        row.extend(deadlifts);
+        row.push(best(deadlift));
+        row.push(best(total));
+
+        entries.push((total, row));
+    }
+
+    // Place lifters by total. Anyone who bombed out is disqualified.
+    entries.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+    let mut rows = vec![HEADERS.iter().map(|h| h.to_string()).collect::<Vec<_>>()];
+    for (i, (total, mut row)) in entries.into_iter().enumerate() {
+        row[0] = if total > 0.0 { format!("{}", i + 1) } else { "DQ".to_string() };
+        rows.push(row);
+    }
+    rows
+}
+
+
+/// Whether text in the given column should be right-aligned.
+fn is_numeric_column(col: usize) -> bool {
+    col == 0 || col >= 4
+}
+
+
+/// Fills a rectangle, clipped to the image.
+fn fill(img: &mut RgbaImage, x: u32, y: u32, width: u32, height: u32, color: Rgba<u8>) {
+    let (img_width, img_height) = img.dimensions();
+    for py in y .. u32::min(y + height, img_height) {
+        for px in x .. u32::min(x + width, img_width) {
+            img.put_pixel(px, py, color);
+        }
+    }
+}
+
+
+/// Draws `text` with its top-left corner at `(x, y)`,
+/// clipped to the rectangle that ends at `(right, bottom)`.
+fn draw_text(img: &mut RgbaImage, style: &Style, text: &str, color: Rgba<u8>,
+             (x, y): (u32, u32), (right, bottom): (u32, u32))
+{
+    if let Font::Sans(size) = style.font {
+        let coverage = sans::render(text, size);
+        let bold = if style.bold { 1 } else { 0 };
+        for cy in 0 .. coverage.height {
+            for cx in 0 .. coverage.width + bold {
+                let (px, py) = (x + cx, y + cy);
+                if px >= right || py >= bottom || px >= img.width() || py >= img.height() {
+                    continue;
+                }
+
+                // Bold text is smeared one pixel to the right.
+                let at = |cx: u32| if cx < coverage.width { coverage.at(cx, cy) } else { 0.0 };
+                let mut alpha = at(cx);
+                if style.bold && cx > 0 {
+                    alpha = f32::max(alpha, at(cx - 1));
+                }
+                if alpha > 0.0 {
+                    let under = *img.get_pixel(px, py);
+                    img.put_pixel(px, py, blend(under, color, f32::min(alpha, 1.0)));
+                }
+            }
+        }
+        return;
+    }
+
+    let scale = style.font_scale;
+    let advance = (font::GLYPH_WIDTH + style.letter_spacing) * scale;
+    let stroke = scale + if style.bold { 1 } else { 0 };
+
+    for (i, ch) in text.chars().enumerate() {
+        let glyph = match font::glyph(ch) {
+            Some(glyph) => glyph,
+            None => continue,
+        };
+        let left = x + advance * i as u32;
+
+        for gy in 0 .. font::GLYPH_HEIGHT {
+            for gx in 0 .. font::GLYPH_WIDTH {
+                if glyph.is_set(gx, gy) {
+                    let px = left + gx * scale;
+                    let py = y + gy * scale;
+                    if px < right && py < bottom {
+                        let width = u32::min(stroke, right - px);
+                        let height = u32::min(scale, bottom - py);
+                        fill(img, px, py, width, height, color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+
+/// Mixes `alpha` of `over` into `under`.
+fn blend(under: Rgba<u8>, over: Rgba<u8>, alpha: f32) -> Rgba<u8> {
+    let mut mixed = under;
+    for i in 0 .. 3 {
+        let channel = under.data[i] as f32 * (1.0 - alpha) + over.data[i] as f32 * alpha;
+        mixed.data[i] = channel.round() as u8;
+    }
+    mixed
+}
+
+
+/// Renders a random table of meet results.
+pub fn generate(rng: &mut Rng, style: &Style) -> Synthetic {
+    let lifters = rng.range(4, 16);
+    let body = random_results(rng, lifters);
+    let num_cols = body[0].len();
+
+    let titles: Vec<String> = (0 .. style.title_rows).map(|i| {
+        if i == 0 {
+            format!("{} {}", rng.choose(MEET_NAMES), rng.range(2010, 2026))
+        } else {
+            format!("{} {}", rng.choose(&["Men", "Women", "Raw", "Equipped"]), rng.choose(DIVISIONS))
+        }
+    }).collect();
+
+    // Size each column to fit its contents unless told otherwise.
+    let mut widths: Vec<u32> = (0 .. num_cols).map(|c| {
+        match style.col_widths.get(c) {
+            Some(&w) => w,
+            None => {
+                let text = body.iter().map(|row| style.text_width(&row[c])).max().unwrap_or(0);
+                text + 2 * style.padding
+            }
+        }
+    }).collect();
+
+    // Title rows span the whole table, so it must be wide enough for them.
+    let line = style.line_width;
+    let title_width = titles.iter().map(|t| style.text_width(t) + 2 * style.padding).max().unwrap_or(0);
+    let interior: u32 = widths.iter().sum::<u32>() + line * (num_cols as u32 - 1);
+    if title_width > interior {
+        widths[num_cols - 1] += title_width - interior;
+    }
+
+    let row_height = style.text_height() + 2 * style.padding;
+    let num_rows = titles.len() + body.len();
+
+    // Gridline positions: each line starts at the given coordinate.
+    let mut col_lines = vec![style.margin];
+    for w in &widths {
+        let last = *col_lines.last().unwrap();
+        col_lines.push(last + line + w);
+    }
+    let row_lines: Vec<u32> = (0 .. num_rows as u32 + 1)
+        .map(|r| style.margin + r * (line + row_height))
+        .collect();
+
+    let width = col_lines[num_cols] + line + style.margin;
+    let height = row_lines[num_rows] + line + style.margin;
+    let mut img: RgbaImage = ImageBuffer::from_pixel(width, height, style.background);
+
+    let mut cells = Vec::new();
+    let mut rows = Vec::new();
+    let mut body = body.into_iter();
+
+    for r in 0 .. num_rows {
+        let y = row_lines[r] + line;
+        let is_title = r < titles.len();
+        let data_index = r as i64 - titles.len() as i64 - 1;
+
+        if let Some(shade) = style.row_shading {
+            if data_index >= 0 && data_index % 2 == 1 {
+                fill(&mut img, col_lines[0] + line, y, col_lines[num_cols] - col_lines[0] - line, row_height, shade);
+            }
+        }
+
+        if is_title {
+            let text = titles[r].clone();
+            let x = col_lines[0] + line;
+            let cell_width = col_lines[num_cols] - x;
+            let text_x = x + cell_width.saturating_sub(style.text_width(&text)) / 2;
+            draw_text(&mut img, style, &text, style.text_color, (text_x, y + style.padding), (x + cell_width, y + row_height));
+
+            let mut csv_row = vec![String::new(); num_cols];
+            csv_row[0] = text.clone();
+            rows.push(csv_row);
+
+            cells.push(TruthCell {
+                row: r as u32, col: 0, col_span: num_cols as u32,
+                x, y, width: cell_width, height: row_height, text,
+            });
+            continue;
+        }
+
+        let mut text_color = style.text_color;
+        if let (Some(header), -1) = (style.header_fill, data_index) {
+            fill(&mut img, col_lines[0] + line, y, col_lines[num_cols] - col_lines[0] - line, row_height, header);
+            text_color = style.background;
+        }
+
+        let row = body.next().unwrap();
+        for c in 0 .. num_cols {
+            let x = col_lines[c] + line;
+            let cell_width = widths[c];
+            let text = &row[c];
+
+            // Only failed attempts are negative. Unless they keep their
+            // minus sign, they are drawn without it and marked instead.
+            let failed = style.failed_mark != FailedMark::Minus && text.starts_with('-');
+            let shown = if failed { &text[1 ..] } else { &text[..] };
+            let mut color = text_color;
+            match style.failed_mark {
+                FailedMark::RedText if failed => color = Rgba([200, 0, 0, 255]),
+                FailedMark::RedFill if failed => fill(&mut img, x, y, cell_width, row_height, Rgba([253, 153, 203, 255])),
+                _ => (),
+            }
+
+            let text_width = u32::min(style.text_width(shown), cell_width.saturating_sub(2 * style.padding));
+            let text_x = if is_numeric_column(c) {
+                x + cell_width.saturating_sub(style.padding + text_width)
+            } else {
+                x + style.padding
+            };
+            let text_y = y + style.padding;
+            draw_text(&mut img, style, shown, color, (text_x, text_y), (x + cell_width, y + row_height));
+
+            let scale = style.stroke_width();
+            match style.failed_mark {
+                FailedMark::Strikethrough if failed => {
+                    // Overhang the text by a stroke, but stay within the cell.
+                    let middle = text_y + (style.text_height() - scale) / 2;
+                    let left = u32::max(text_x.saturating_sub(scale), x);
+                    let right = u32::min(text_x + text_width + scale, x + cell_width);
+                    fill(&mut img, left, middle, right.saturating_sub(left), scale, color);
+                }
+                FailedMark::Cross if failed => {
+                    let (w, h) = (text_width.saturating_sub(scale), style.text_height() - scale);
+                    let steps = u32::max(w, h);
+                    for i in 0 ..= steps {
+                        fill(&mut img, text_x + i * w / steps, text_y + i * h / steps, scale, scale, color);
+                        fill(&mut img, text_x + i * w / steps, text_y + h - i * h / steps, scale, scale, color);
+                    }
+                }
+                _ => (),
+            }
+
+            cells.push(TruthCell {
+                row: r as u32, col: c as u32, col_span: 1,
+                x, y, width: cell_width, height: row_height, text: text.clone(),
+            });
+        }
+        rows.push(row);
+    }
+
+    // Horizontal gridlines span the table.
+    for &y in &row_lines {
+        fill(&mut img, col_lines[0], y, col_lines[num_cols] + line - col_lines[0], line, style.line_color);
+    }
+
+    // Vertical gridlines stop at title rows, which are merged.
+    let grid_top = row_lines[titles.len()];
+    for (c, &x) in col_lines.iter().enumerate() {
+        let top = if c == 0 || c == num_cols { row_lines[0] } else { grid_top };
+        fill(&mut img, x, top, line, row_lines[num_rows] + line - top, style.line_color);
+
+        if style.header_fill.is_some() && c > 0 && c < num_cols {
+            fill(&mut img, x, grid_top + line, line, row_height, style.background);
+        }
+    }
+
+    if style.spreadsheet {
+        let (window, dx, dy) = draw_window(&img, style, &col_lines, &row_lines);
+        img = window;
+        for cell in &mut cells {
+            cell.x = (cell.x as i64 + dx) as u32;
+            cell.y = (cell.y as i64 + dy) as u32;
+        }
+    }
+
+    Synthetic {
+        image: DynamicImage::ImageRgba8(img),
+        cells,
+        rows,
+    }
+}
+
+
+/// The name of a spreadsheet column, counting from zero: A, B, ..., Z, AA.
+fn column_name(col: usize) -> String {
+    let mut name = String::new();
+    let mut n = col + 1;
+    while n > 0 {
+        name.insert(0, (b'A' + ((n - 1) % 26) as u8) as char);
+        n = (n - 1) / 26;
+    }
+    name
+}
+
+
+/// Places the grid of a rendered table in a spreadsheet window, given the
+/// positions of its gridlines. Returns the window and how far the grid moved.
+fn draw_window(table: &RgbaImage, style: &Style, col_lines: &[u32], row_lines: &[u32]) -> (RgbaImage, i64, i64) {
+    let chrome = Rgba([243, 243, 243, 255]);
+    let button = Rgba([120, 120, 120, 255]);
+    let header = Rgba([233, 233, 233, 255]);
+    let label = Rgba([80, 80, 80, 255]);
+    let thumb = Rgba([212, 212, 212, 255]);
+    const TOOLBAR_PX: u32 = 64;
+    const GAP_PX: u32 = 12;
+    const SCROLLBAR_PX: u32 = 14;
+    const STATUS_BAR_PX: u32 = 20;
+    const EXTRA_ROWS: u32 = 2;
+    const EXTRA_COL_PX: u32 = 64;
+
+    let line = u32::max(style.line_width, 1);
+    let row_height = style.text_height() + 2 * style.padding;
+    let label_width = style.text_width("99") + 2 * style.padding;
+    let (num_cols, num_rows) = (col_lines.len() - 1, row_lines.len() - 1);
+    let grid_width = col_lines[num_cols] + line - col_lines[0];
+    let grid_height = row_lines[num_rows] + line - row_lines[0];
+
+    // The grid's top-left corner, below the column letters and right of the row numbers.
+    let header_top = TOOLBAR_PX + GAP_PX;
+    let (gx, gy) = (line + label_width, header_top + line + row_height);
+    let sheet_right = gx + grid_width + EXTRA_COL_PX + line;
+    let sheet_bottom = gy + grid_height + EXTRA_ROWS * (row_height + line);
+    let width = sheet_right + SCROLLBAR_PX;
+    let height = sheet_bottom + STATUS_BAR_PX;
+
+    let mut img: RgbaImage = ImageBuffer::from_pixel(width, height, style.background);
+    for y in 0 .. grid_height {
+        for x in 0 .. grid_width {
+            img.put_pixel(gx + x, gy + y, *table.get_pixel(col_lines[0] + x, row_lines[0] + y));
+        }
+    }
+
+    // A toolbar of buttons above a formula bar.
+    fill(&mut img, 0, 0, width, TOOLBAR_PX, chrome);
+    for i in 0 .. 8 {
+        let x = 8 + 28 * i;
+        fill(&mut img, x, 6, 20, 1, button);
+        fill(&mut img, x, 25, 20, 1, button);
+        fill(&mut img, x, 6, 1, 20, button);
+        fill(&mut img, x + 19, 6, 1, 20, button);
+    }
+    fill(&mut img, 60, 34, width - 68, 24, style.background);
+    fill(&mut img, 60, 34, width - 68, 1, button);
+    fill(&mut img, 60, 57, width - 68, 1, button);
+
+    // Column letters, including the empty column past the data.
+    let mut lefts: Vec<u32> = col_lines.iter().map(|&x| gx + x - col_lines[0]).collect();
+    lefts.push(gx + grid_width + EXTRA_COL_PX);
+    fill(&mut img, 0, header_top + line, sheet_right, row_height, header);
+    for c in 0 .. lefts.len() - 1 {
+        let (x, right) = (lefts[c] + line, lefts[c + 1]);
+        let name = column_name(c);
+        let text_x = x + (right - x).saturating_sub(style.text_width(&name)) / 2;
+        draw_text(&mut img, style, &name, label, (text_x, header_top + line + style.padding), (right, gy));
+    }
+
+    // Row numbers, including the empty rows past the data.
+    let mut tops: Vec<u32> = row_lines.iter().map(|&y| gy + y - row_lines[0]).collect();
+    for _ in 0 .. EXTRA_ROWS {
+        let last = *tops.last().unwrap();
+        tops.push(last + row_height + line);
+    }
+    fill(&mut img, 0, gy, gx, sheet_bottom - gy, header);
+    for r in 0 .. tops.len() - 1 {
+        let (y, bottom) = (tops[r] + line, tops[r + 1]);
+        let number = format!("{}", r + 1);
+        let text_x = line + label_width.saturating_sub(style.text_width(&number)) / 2;
+        draw_text(&mut img, style, &number, label, (text_x, y + style.padding), (gx, bottom));
+    }
+
+    // Gridlines around the headers and the empty cells.
+    fill(&mut img, 0, header_top, sheet_right, line, style.line_color);
+    for &y in &tops {
+        fill(&mut img, 0, y, gx, line, style.line_color);
+        fill(&mut img, gx + grid_width, y, EXTRA_COL_PX, line, style.line_color);
+    }
+    for &y in &tops[num_rows ..] {
+        fill(&mut img, 0, y, sheet_right, line, style.line_color);
+    }
+    fill(&mut img, 0, header_top, line, sheet_bottom - header_top, style.line_color);
+    fill(&mut img, sheet_right - line, header_top, line, sheet_bottom - header_top, style.line_color);
+    for &x in &lefts {
+        fill(&mut img, x, header_top, line, gy - header_top, style.line_color);
+        fill(&mut img, x, gy + grid_height, line, sheet_bottom - gy - grid_height, style.line_color);
+    }
+
+    // A scrollbar beside the sheet, and a status bar below it.
+    fill(&mut img, sheet_right, header_top, SCROLLBAR_PX, sheet_bottom - header_top, chrome);
+    fill(&mut img, sheet_right + 3, header_top + 20, SCROLLBAR_PX - 6, 40, thumb);
+    fill(&mut img, 0, sheet_bottom, width, STATUS_BAR_PX, chrome);
+
+    (img, gx as i64 - col_lines[0] as i64, gy as i64 - row_lines[0] as i64)
+}
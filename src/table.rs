@@ -0,0 +1,288 @@
+//! The grid of cells detected in an image.
+
+use std::ops::Range;
+
+use Cell;
+use perspective::Homography;
+
+/// A rectangle of the image that holds a table.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Bounds {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Bounds {
+    /// The column just past the right edge.
+    pub fn right(&self) -> u32 {
+        self.x + self.width
+    }
+
+    /// The row just past the bottom edge.
+    pub fn bottom(&self) -> u32 {
+        self.y + self.height
+    }
+}
+
+
+/// A table of cells that share global row and column boundaries.
+///
+/// Every row is cut by the same column separators, so a given column
+/// number refers to the same horizontal span in every row.
+#[derive(Clone, Debug)]
+pub struct Table {
+    /// Y-coordinates of the lines between rows, top to bottom.
+    row_separators: Vec<u32>,
+    /// X-coordinates of the lines between columns, left to right.
+    col_separators: Vec<u32>,
+    rows: u32,
+    cols: u32,
+    /// Every cell, sorted by (row, col).
+    cells: Vec<Cell>,
+    /// The mean darkness threshold used to find features.
+    threshold: f32,
+    /// The rotation, in degrees, that was removed before detection.
+    skew: f32,
+    /// Maps cell coordinates back to the original image.
+    to_original: Homography,
+    /// Whether the image was light on dark, and was inverted before detection.
+    inverted: bool,
+    /// Where the table lies in the image.
+    bounds: Bounds,
+}
+
+impl Table {
+    pub fn new(row_separators: Vec<u32>,
+               col_separators: Vec<u32>,
+               rows: u32,
+               cols: u32,
+               mut cells: Vec<Cell>) -> Table
+    {
+        cells.sort_by_key(|c| (c.row, c.col));
+        Table { row_separators, col_separators, rows, cols, cells, threshold: 0.0, skew: 0.0,
+                to_original: Homography::identity(), inverted: false,
+                bounds: Bounds::default() }
+    }
+
+    /// Records the darkness threshold that was used to find the table.
+    pub fn with_threshold(mut self, threshold: f32) -> Table {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Records the skew that was removed from the image.
+    pub fn with_skew(mut self, skew: f32) -> Table {
+        self.skew = skew;
+        self
+    }
+
+    /// Records the transform from cell coordinates to the original image.
+    pub fn with_transform(mut self, to_original: Homography) -> Table {
+        self.to_original = to_original;
+        self
+    }
+
+    /// Records where the table lies in the image.
+    pub fn with_bounds(mut self, bounds: Bounds) -> Table {
+        self.bounds = bounds;
+        self
+    }
+
+    /// Moves the table right by `dx` and down by `dy`, for a table
+    /// that was found in a crop of a larger image.
+    pub fn with_offset(mut self, dx: u32, dy: u32) -> Table {
+        for cell in &mut self.cells {
+            cell.x += dx;
+            cell.y += dy;
+        }
+        for y in &mut self.row_separators {
+            *y += dy;
+        }
+        for x in &mut self.col_separators {
+            *x += dx;
+        }
+        self.bounds.x += dx;
+        self.bounds.y += dy;
+        self
+    }
+
+    /// Records whether the image was inverted before detection.
+    pub fn with_inverted(mut self, inverted: bool) -> Table {
+        self.inverted = inverted;
+        self
+    }
+
+    /// Scores the borders of every cell, as in `Cell::structure`.
+    pub fn with_structure<F: Fn(&Cell) -> f32>(mut self, score: F) -> Table {
+        for cell in &mut self.cells {
+            cell.structure = score(cell);
+        }
+        self
+    }
+
+    /// Maps a point in cell coordinates back to the original image,
+    /// undoing any deskewing and perspective correction.
+    pub fn to_original(&self, x: f32, y: f32) -> (f32, f32) {
+        self.to_original.apply(x, y)
+    }
+
+    /// The smallest box around the cell in the original image.
+    pub fn original_bounds(&self, cell: &Cell) -> Bounds {
+        let (x0, y0) = (cell.x as f32, cell.y as f32);
+        let (x1, y1) = ((cell.x + cell.width) as f32, (cell.y + cell.height) as f32);
+        let corners = [self.to_original(x0, y0), self.to_original(x1, y0),
+                       self.to_original(x1, y1), self.to_original(x0, y1)];
+
+        let left = corners.iter().map(|c| c.0).fold(f32::INFINITY, f32::min).max(0.0);
+        let top = corners.iter().map(|c| c.1).fold(f32::INFINITY, f32::min).max(0.0);
+        let right = corners.iter().map(|c| c.0).fold(0.0, f32::max);
+        let bottom = corners.iter().map(|c| c.1).fold(0.0, f32::max);
+        Bounds {
+            x: left.round() as u32,
+            y: top.round() as u32,
+            width: (right - left).max(0.0).round() as u32,
+            height: (bottom - top).max(0.0).round() as u32,
+        }
+    }
+
+    /// How far the image was rotated counterclockwise, in degrees.
+    /// It was straightened before detection. Zero if it was left as it is.
+    pub fn skew(&self) -> f32 {
+        self.skew
+    }
+
+    /// Whether the image had light text and gridlines on a dark background.
+    /// It was inverted before detection.
+    pub fn is_inverted(&self) -> bool {
+        self.inverted
+    }
+
+    /// The mean darkness threshold used to find features, from 0 to 255.
+    /// Pixels at or below it were treated as ink.
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    /// Where the table lies in the image that its cells refer to.
+    pub fn bounds(&self) -> Bounds {
+        self.bounds
+    }
+
+    /// The number of rows in the table.
+    pub fn rows(&self) -> u32 {
+        self.rows
+    }
+
+    /// The number of columns in the table.
+    pub fn cols(&self) -> u32 {
+        self.cols
+    }
+
+    /// The cell that begins at the given row and column, if any.
+    pub fn get(&self, row: u32, col: u32) -> Option<&Cell> {
+        self.cells.binary_search_by_key(&(row, col), |c| (c.row, c.col))
+            .ok()
+            .map(|i| &self.cells[i])
+    }
+
+    /// The cell that covers the given row and column, which may be a
+    /// merged cell that begins above or to the left.
+    pub fn covering(&self, row: u32, col: u32) -> Option<&Cell> {
+        self.cells.iter()
+            .take_while(|c| c.row <= row)
+            .find(|c| c.covers(row, col))
+    }
+
+    /// Every cell, sorted by (row, col).
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    /// The cells that begin in a single row, left to right.
+    pub fn row(&self, row: u32) -> &[Cell] {
+        let start = self.cells.iter().position(|c| c.row == row).unwrap_or(self.cells.len());
+        let len = self.cells[start..].iter().take_while(|c| c.row == row).count();
+        &self.cells[start .. start + len]
+    }
+
+    /// Y-coordinates of the lines between rows, top to bottom.
+    pub fn row_separators(&self) -> &[u32] {
+        &self.row_separators
+    }
+
+    /// X-coordinates of the lines between columns, left to right.
+    pub fn col_separators(&self) -> &[u32] {
+        &self.col_separators
+    }
+
+    /// The part of the table in the given rows and columns, renumbered
+    /// from zero. Merged cells that reach outside it are cut short.
+    pub fn slice(&self, rows: Range<u32>, cols: Range<u32>) -> Table {
+        // Where each row and column starts and ends, from any cell that
+        // starts or ends there. Edges of merged cells are moved to them.
+        let top = |row: u32| self.cells.iter().find(|c| c.row == row).map(|c| c.y);
+        let bottom = |row: u32| self.cells.iter().find(|c| c.row + c.row_span == row + 1).map(|c| c.y + c.height);
+        let left = |col: u32| self.cells.iter().find(|c| c.col == col).map(|c| c.x);
+        let right = |col: u32| self.cells.iter().find(|c| c.col + c.col_span == col + 1).map(|c| c.x + c.width);
+
+        let mut cells = Vec::new();
+        for cell in &self.cells {
+            let (row_start, row_end) = (u32::max(cell.row, rows.start), u32::min(cell.row + cell.row_span, rows.end));
+            let (col_start, col_end) = (u32::max(cell.col, cols.start), u32::min(cell.col + cell.col_span, cols.end));
+            if row_start >= row_end || col_start >= col_end {
+                continue;
+            }
+
+            let x = if col_start > cell.col { left(col_start).unwrap_or(cell.x) } else { cell.x };
+            let y = if row_start > cell.row { top(row_start).unwrap_or(cell.y) } else { cell.y };
+            let x_end = if col_end < cell.col + cell.col_span { right(col_end - 1) } else { None }
+                .unwrap_or(cell.x + cell.width);
+            let y_end = if row_end < cell.row + cell.row_span { bottom(row_end - 1) } else { None }
+                .unwrap_or(cell.y + cell.height);
+
+            cells.push(Cell {
+                row: row_start - rows.start,
+                col: col_start - cols.start,
+                row_span: row_end - row_start,
+                col_span: col_end - col_start,
+                x,
+                y,
+                width: x_end.saturating_sub(x),
+                height: y_end.saturating_sub(y),
+                structure: cell.structure,
+            });
+        }
+
+        let bounds = match (cells.iter().map(|c| c.x).min(), cells.iter().map(|c| c.y).min()) {
+            (Some(x), Some(y)) => {
+                let right = cells.iter().map(|c| c.x + c.width).max().unwrap_or(x);
+                let bottom = cells.iter().map(|c| c.y + c.height).max().unwrap_or(y);
+                Bounds { x, y, width: right - x, height: bottom - y }
+            }
+            _ => Bounds { x: self.bounds.x, y: self.bounds.y, width: 0, height: 0 },
+        };
+
+        let row_separators = self.row_separators.iter().cloned()
+            .filter(|&y| y >= bounds.y && y <= bounds.bottom())
+            .collect();
+        let col_separators = self.col_separators.iter().cloned()
+            .filter(|&x| x >= bounds.x && x <= bounds.right())
+            .collect();
+
+        cells.sort_by_key(|c| (c.row, c.col));
+        Table {
+            row_separators,
+            col_separators,
+            rows: rows.end.saturating_sub(rows.start),
+            cols: cols.end.saturating_sub(cols.start),
+            cells,
+            threshold: self.threshold,
+            skew: self.skew,
+            to_original: self.to_original,
+            inverted: self.inverted,
+            bounds,
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! An `OcrEngine` backed by the system libtesseract.
+//!
+//! Only built with the `tesseract` feature.
+
+use image::{DynamicImage, GenericImage};
+use libc::*;
+
+use ocr::{OcrEngine, Recognition};
+
+use std::error::Error;
+use std::ffi::{CStr, CString};
+use std::ptr::null;
+
+mod capi {
+    use libc::*;
+
+    #[repr(C)]
+    pub struct TessBaseAPI {
+        #[allow(dead_code)]
+        placeholder: c_int,
+    }
+
+    extern {
+        pub fn TessBaseAPICreate() -> *mut TessBaseAPI;
+        pub fn TessBaseAPIDelete(api: *mut TessBaseAPI);
+        pub fn TessBaseAPIInit3(api: *mut TessBaseAPI, datapath: *const c_char, language: *const c_char) -> c_int;
+        pub fn TessBaseAPIEnd(api: *mut TessBaseAPI);
+
+        pub fn TessBaseAPISetPageSegMode(api: *mut TessBaseAPI, mode: c_int);
+        pub fn TessBaseAPISetVariable(api: *mut TessBaseAPI, name: *const c_char, value: *const c_char) -> c_int;
+
+        pub fn TessBaseAPISetImage(api: *mut TessBaseAPI, imagedata: *const c_uchar,
+                                   width: c_int, height: c_int,
+                                   bytes_per_pixel: c_int, bytes_per_line: c_int);
+        pub fn TessBaseAPIGetUTF8Text(api: *mut TessBaseAPI) -> *mut c_char;
+        pub fn TessBaseAPIMeanTextConf(api: *mut TessBaseAPI) -> c_int;
+        pub fn TessDeleteText(text: *mut c_char);
+    }
+}
+
+/// Tesseract page segmentation modes that make sense for a single cell.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PageSegMode {
+    /// Treat the image as a single text line.
+    SingleLine = 7,
+    /// Treat the image as a single word.
+    SingleWord = 8,
+    /// Treat the image as a single text line, bypassing Tesseract's heuristics.
+    RawLine = 13,
+}
+
+/// Recognizes cells with libtesseract.
+pub struct TesseractEngine {
+    api: *mut capi::TessBaseAPI,
+    /// Characters allowed by default, or `None` to allow anything.
+    whitelist: Option<String>,
+}
+
+impl TesseractEngine {
+    /// Initializes Tesseract for the given language, such as `"eng"`.
+    /// Trained data is looked up through `TESSDATA_PREFIX`.
+    pub fn new(language: &str) -> Result<TesseractEngine, Box<Error>> {
+        let c_language = CString::new(language)?;
+
+        let api = unsafe { capi::TessBaseAPICreate() };
+        if api.is_null() {
+            return Err("Could not create a Tesseract instance.".into());
+        }
+
+        if unsafe { capi::TessBaseAPIInit3(api, null(), c_language.as_ptr()) } != 0 {
+            unsafe { capi::TessBaseAPIDelete(api) };
+            return Err(format!("Could not initialize Tesseract for language {:?}.", language).into());
+        }
+
+        let mut engine = TesseractEngine { api, whitelist: None };
+        engine.set_page_seg_mode(PageSegMode::SingleLine);
+        Ok(engine)
+    }
+
+    pub fn set_page_seg_mode(&mut self, mode: PageSegMode) {
+        unsafe { capi::TessBaseAPISetPageSegMode(self.api, mode as c_int) }
+    }
+
+    /// Sets the characters allowed when no per-call whitelist is given.
+    pub fn set_whitelist(&mut self, whitelist: Option<&str>) {
+        self.whitelist = whitelist.map(|s| s.to_string());
+    }
+
+    fn set_variable(&mut self, name: &str, value: &str) -> Result<(), Box<Error>> {
+        let c_name = CString::new(name)?;
+        let c_value = CString::new(value)?;
+        if unsafe { capi::TessBaseAPISetVariable(self.api, c_name.as_ptr(), c_value.as_ptr()) } == 0 {
+            return Err(format!("Tesseract rejected variable {}.", name).into());
+        }
+        Ok(())
+    }
+
+    /// Recognizes `cell`, allowing only the characters in `whitelist`.
+    /// If `whitelist` is `None`, the engine's default whitelist is used.
+    pub fn recognize_with_whitelist(&mut self, cell: &DynamicImage, whitelist: Option<&str>)
+        -> Result<Recognition, Box<Error>>
+    {
+        let whitelist = match whitelist {
+            Some(chars) => chars.to_string(),
+            None => self.whitelist.clone().unwrap_or_default(),
+        };
+        self.set_variable("tessedit_char_whitelist", &whitelist)?;
+
+        let (width, height) = cell.dimensions();
+        let gray = cell.to_luma();
+
+        unsafe {
+            capi::TessBaseAPISetImage(self.api, gray.as_ptr(),
+                                      width as c_int, height as c_int,
+                                      1, width as c_int);
+        }
+
+        let text = unsafe {
+            let ptr = capi::TessBaseAPIGetUTF8Text(self.api);
+            if ptr.is_null() {
+                return Err("Tesseract failed to recognize the cell.".into());
+            }
+            let text = CStr::from_ptr(ptr).to_string_lossy().trim().to_string();
+            capi::TessDeleteText(ptr);
+            text
+        };
+
+        // Tesseract gives no confidence for a cell without text.
+        if text.is_empty() {
+            return Ok(Recognition::empty());
+        }
+        let confidence = unsafe { capi::TessBaseAPIMeanTextConf(self.api) };
+        Ok(Recognition::new(text, (confidence as f32 / 100.0).max(0.0)))
+    }
+}
+
+impl OcrEngine for TesseractEngine {
+    fn recognize(&mut self, cell: &DynamicImage) -> Result<Recognition, Box<Error>> {
+        self.recognize_with_whitelist(cell, None)
+    }
+}
+
+impl Drop for TesseractEngine {
+    fn drop(&mut self) {
+        unsafe {
+            capi::TessBaseAPIEnd(self.api);
+            capi::TessBaseAPIDelete(self.api);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+extern crate img2csv;
+extern crate image;
+
+use img2csv::augment::{self, Degradation};
+use img2csv::synth::{self, Rng, Style};
+
+
+#[test]
+fn test_degradations_keep_cells_in_sync() {
+    use image::GenericImage;
+
+    let mut rng = Rng::new(3);
+    let mut table = synth::generate(&mut rng, &Style::default());
+    let before = table.cells.clone();
+
+    augment::apply_all(&mut table, &[
+        Degradation::Jpeg { quality: 70 },
+        Degradation::Rescale { factor: 2.0 },
+        Degradation::ChromaBleed { radius: 2 },
+    ], &mut rng).unwrap();
+
+    // Upscaling exactly doubles every rectangle.
+    for (a, b) in before.iter().zip(table.cells.iter()) {
+        assert_eq!((b.x, b.y, b.width, b.height), (2 * a.x, 2 * a.y, 2 * a.width, 2 * a.height));
+    }
+
+    augment::apply_all(&mut table, &[Degradation::Rotate { degrees: 1.0 }], &mut rng).unwrap();
+    let (width, height) = table.image.dimensions();
+    for cell in &table.cells {
+        assert!(cell.x + cell.width <= width && cell.y + cell.height <= height);
+    }
+}
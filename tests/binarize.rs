@@ -0,0 +1,61 @@
+extern crate img2csv;
+extern crate image;
+
+mod common;
+
+use common::assert_same_grid;
+use image::Rgba;
+use img2csv::synth::{self, Rng, Style};
+
+
+#[test]
+fn test_colored_lines_and_shaded_rows() {
+    let mut rng = Rng::new(21);
+    let styles = [
+        // Light blue rules are too pale for a fixed threshold.
+        Style { line_color: Rgba([120, 170, 240, 255]), ..Style::default() },
+        // Green rules are as bright as the lavender stripes they cross.
+        Style {
+            line_color: Rgba([160, 240, 113, 255]),
+            row_shading: Some(Rgba([212, 212, 242, 255])),
+            ..Style::default()
+        },
+        // Darker stripes must not turn into features.
+        Style {
+            line_color: Rgba([90, 90, 90, 255]),
+            row_shading: Some(Rgba([150, 150, 150, 255])),
+            ..Style::default()
+        },
+    ];
+
+    for style in &styles {
+        let table = synth::generate(&mut rng, style);
+        let detected = img2csv::get_cells(&table.image);
+        assert_same_grid(&table, &detected);
+
+        for (truth, cell) in table.cells.iter().zip(detected.cells()) {
+            assert_eq!((cell.row, cell.col, cell.col_span), (truth.row, truth.col, truth.col_span));
+        }
+    }
+}
+
+
+#[test]
+fn test_dark_mode_is_inverted() {
+    let mut rng = Rng::new(37);
+    let style = Style {
+        line_color: Rgba([90, 90, 90, 255]),
+        text_color: Rgba([220, 220, 220, 255]),
+        background: Rgba([30, 30, 30, 255]),
+        row_shading: Some(Rgba([45, 45, 50, 255])),
+        ..Style::default()
+    };
+
+    let table = synth::generate(&mut rng, &style);
+    let detected = img2csv::get_cells(&table.image);
+    assert!(detected.is_inverted());
+    assert_same_grid(&table, &detected);
+
+    let light = img2csv::get_cells(&synth::generate(&mut rng, &Style::default()).image);
+    assert!(!light.is_inverted());
+}
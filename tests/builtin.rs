@@ -0,0 +1,139 @@
+extern crate img2csv;
+extern crate image;
+
+use img2csv::builtin::BuiltinEngine;
+use img2csv::font;
+use img2csv::ocr::{NullEngine, OcrEngine, Recognition};
+use img2csv::sans;
+use image::{DynamicImage, GenericImage, Rgba};
+
+
+/// Draws `text` in the built-in font, black on white, with each
+/// font pixel covering a `scale` by `scale` square.
+fn render(text: &str, scale: u32) -> DynamicImage {
+    let margin = 3 * scale;
+    let advance = (font::GLYPH_WIDTH + 1) * scale;
+    let width = 2 * margin + advance * text.chars().count() as u32;
+    let height = 2 * margin + font::GLYPH_HEIGHT * scale;
+
+    let mut img = DynamicImage::new_rgba8(width, height);
+    for (x, y, _) in img.clone().pixels() {
+        img.put_pixel(x, y, Rgba([255, 255, 255, 255]));
+    }
+
+    for (i, ch) in text.chars().enumerate() {
+        let glyph = match font::glyph(ch) {
+            Some(glyph) => glyph,
+            None => continue,
+        };
+        let left = margin + advance * i as u32;
+
+        for gy in 0 .. font::GLYPH_HEIGHT {
+            for gx in 0 .. font::GLYPH_WIDTH {
+                if !glyph.is_set(gx, gy) {
+                    continue;
+                }
+                for dy in 0 .. scale {
+                    for dx in 0 .. scale {
+                        img.put_pixel(left + gx * scale + dx, margin + gy * scale + dy,
+                                      Rgba([0, 0, 0, 255]));
+                    }
+                }
+            }
+        }
+    }
+
+    img.grayscale()
+}
+
+
+/// Draws `text` in the `sans` typeface at `size` pixels to the em,
+/// anti-aliased black on white.
+fn render_sans(text: &str, size: f32) -> DynamicImage {
+    let margin = 4;
+    let coverage = sans::render(text, size);
+
+    let mut img = DynamicImage::new_rgba8(coverage.width + 2 * margin, coverage.height + 2 * margin);
+    for (x, y, _) in img.clone().pixels() {
+        img.put_pixel(x, y, Rgba([255, 255, 255, 255]));
+    }
+    for y in 0 .. coverage.height {
+        for x in 0 .. coverage.width {
+            let luma = (255.0 * (1.0 - coverage.at(x, y).min(1.0))).round() as u8;
+            img.put_pixel(margin + x, margin + y, Rgba([luma, luma, luma, 255]));
+        }
+    }
+
+    img.grayscale()
+}
+
+
+/// Draws a shape, black on white, where `is_ink` holds for offsets from the center.
+fn render_shape<F: Fn(i32, i32) -> bool>(is_ink: F) -> DynamicImage {
+    let mut img = DynamicImage::new_rgba8(48, 48);
+    for (x, y, _) in img.clone().pixels() {
+        let luma = if is_ink(x as i32 - 24, y as i32 - 24) { 0 } else { 255 };
+        img.put_pixel(x, y, Rgba([luma, luma, luma, 255]));
+    }
+    img.grayscale()
+}
+
+
+#[test]
+fn test_builtin_recognizes_rendered_text() {
+    let mut engine = BuiltinEngine::new();
+
+    for text in &["182.5", "-200", "1st", "Smith", "Open M1 (83kg)"] {
+        for scale in 1 .. 4 {
+            let result = engine.recognize(&render(text, scale)).unwrap();
+            assert_eq!(&result.text, text, "at scale {}", scale);
+            assert!(result.confidence > 0.9);
+        }
+    }
+}
+
+
+#[test]
+fn test_builtin_recognizes_a_real_typeface() {
+    let mut engine = BuiltinEngine::new();
+
+    // The templates are drawn far larger, so none of these
+    // anti-aliased glyphs matches one pixel for pixel.
+    for text in &["-200", "Smith", "2,048", "Mean: 56.25%"] {
+        for &size in &[13.5, 19.0, 23.0, 30.0] {
+            let result = engine.recognize(&render_sans(text, size)).unwrap();
+            assert_eq!(&result.text, text, "at {} pixels to the em", size);
+            assert!(result.confidence > 0.7, "{:?} at {} pixels to the em", result, size);
+        }
+    }
+}
+
+
+#[test]
+fn test_builtin_doubts_what_is_not_text() {
+    let mut engine = BuiltinEngine::new();
+
+    // Nothing in either font is a solid disk.
+    let disk = engine.recognize(&render_shape(|x, y| x * x + y * y < 256)).unwrap();
+    assert_eq!(disk, Recognition::new("?", 0.0));
+
+    // Shapes that come closer to a glyph are still not trusted.
+    let block = engine.recognize(&render_shape(|x, y| x.abs() < 16 && y.abs() < 16)).unwrap();
+    assert!(block.confidence < 0.7, "{:?}", block);
+    let checkers = engine.recognize(&render_shape(|x, y| {
+        x.abs() < 16 && y.abs() < 16 && (x / 4 + y / 4 + 10) % 2 == 0
+    })).unwrap();
+    assert!(checkers.confidence < 0.7, "{:?}", checkers);
+}
+
+
+#[test]
+fn test_builtin_blank_cell_is_empty() {
+    let mut engine = BuiltinEngine::new();
+    let result = engine.recognize(&render("", 2)).unwrap();
+    assert_eq!(result, Recognition::empty());
+    assert_eq!(result.confidence, 1.0);
+
+    // Engines agree on blank cells, so they are not sent for review.
+    assert_eq!(NullEngine.recognize(&render("", 2)).unwrap(), result);
+}
@@ -0,0 +1,131 @@
+extern crate img2csv;
+extern crate image;
+
+mod common;
+
+use common::{assert_same_grid, convert_to_csv, test_dir};
+use image::{DynamicImage, Rgba};
+use img2csv::synth::{self, Rng, Style};
+
+
+#[test]
+fn test_spreadsheet_window_is_cropped() {
+    let mut rng = Rng::new(43);
+    let styles = [
+        Style { spreadsheet: true, ..Style::default() },
+        Style { spreadsheet: true, title_rows: 0, line_color: Rgba([190, 190, 190, 255]), ..Style::default() },
+    ];
+
+    for style in &styles {
+        let table = synth::generate(&mut rng, style);
+        let detected = img2csv::get_cells(&table.image);
+        assert_same_grid(&table, &detected);
+
+        // Row 0 is the first row of data, not the column letters.
+        let first = &table.cells[0];
+        let cell = detected.get(0, 0).unwrap();
+        assert!((cell.x as i64 - first.x as i64).abs() <= 2);
+        assert!((cell.y as i64 - first.y as i64).abs() <= 2);
+    }
+}
+
+
+#[test]
+fn test_spreadsheet_headers_in_another_font() {
+    use img2csv::ocr::{MockEngine, NullEngine, OcrEngine, Recognition};
+    use std::error::Error;
+
+    /// Reads every cell as "7", as if it were sure.
+    struct Sevens;
+
+    impl OcrEngine for Sevens {
+        fn recognize(&mut self, _cell: &DynamicImage) -> Result<Recognition, Box<Error>> {
+            Ok(Recognition::new("7", 1.0))
+        }
+    }
+
+    let mut rng = Rng::new(43);
+    let style = Style { spreadsheet: true, ..Style::default() };
+    let table = synth::generate(&mut rng, &style);
+    let mut img = table.image.to_rgba();
+
+    // Redraw the column letters and row numbers slanted and twice as bold,
+    // as another font would draw them, without touching their gridlines.
+    let (label, fill) = (Rgba([80, 80, 80, 255]), Rgba([233, 233, 233, 255]));
+    let top = table.cells[0].y;
+    let pitch = style.text_height() + 2 * style.padding + style.line_width;
+    let ink: Vec<(u32, u32)> = img.enumerate_pixels().filter(|p| *p.2 == label).map(|p| (p.0, p.1)).collect();
+    for &(x, y) in &ink {
+        img.put_pixel(x, y, fill);
+    }
+    for &(x, y) in &ink {
+        let slant = (pitch - (y + pitch - top) % pitch) / 4;
+        for nx in x + slant .. x + slant + 2 {
+            if nx >= 3 && *img.get_pixel(nx - 3, y) == fill {
+                img.put_pixel(nx - 3, y, label);
+            }
+        }
+    }
+    let img = DynamicImage::ImageRgba8(img);
+    assert_same_grid(&table, &img2csv::get_cells(&img));
+
+    // An engine that cannot read the labels leaves them to geometry, but
+    // one that reads them as letters and numbers that do not count up
+    // keeps them in the table. A mock's queue is only for output cells.
+    let dir = test_dir("sheet-headers");
+    let rows = |engine: &mut OcrEngine| convert_to_csv(&img, &dir, "", engine).lines().count();
+    assert_eq!(rows(&mut NullEngine), table.rows.len());
+    assert_eq!(rows(&mut MockEngine::with_responses(vec![Recognition::new("7", 1.0); 1000])), table.rows.len());
+    assert!(rows(&mut Sevens) > table.rows.len());
+}
+
+
+#[test]
+fn test_builtin_engine_agrees_with_sheet_headers() {
+    use img2csv::builtin::BuiltinEngine;
+    use img2csv::synth::Font;
+
+    let dir = test_dir("builtin-sheet-headers");
+    let mut engine = BuiltinEngine::new();
+
+    let mut rng = Rng::new(44);
+    for &font in &[Font::Bitmap, Font::Sans(16.0)] {
+        let style = Style { spreadsheet: true, font, ..Style::default() };
+        let table = synth::generate(&mut rng, &style);
+        let csv = convert_to_csv(&table.image, &dir, "", &mut engine);
+
+        // The letters and numbers read as counting up, so neither the
+        // row of letters nor the column of numbers is left in the table.
+        let mut truth = Vec::new();
+        table.write_csv(&mut truth).unwrap();
+        let truth = String::from_utf8(truth).unwrap();
+        assert_eq!(csv.lines().count(), truth.lines().count(), "in {:?}", font);
+        let fields = |text: &str| text.lines().next().map(|line| line.split(',').count());
+        assert_eq!(fields(&csv), fields(&truth), "in {:?}", font);
+    }
+}
+
+
+#[test]
+fn test_boxed_table_with_short_inner_rules() {
+    use common::draw_grid;
+
+    // An outer box whose inner rules are stubs too short to cross it, so
+    // that no cell starts in the top row or left column alone.
+    let (width, height, stub) = (460, 340, 60);
+    let mut lines = vec![
+        (2, 2, width - 4, 1), (2, height - 3, width - 4, 1),
+        (2, 2, 1, height - 4), (width - 3, 2, 1, height - 4),
+    ];
+    for &y in &[120, 220] {
+        lines.push((2, y, stub, 1));
+        lines.push((width - 2 - stub, y, stub, 1));
+    }
+    for &x in &[170, 320] {
+        lines.push((x, 2, 1, stub));
+        lines.push((x, height - 2 - stub, 1, stub));
+    }
+
+    let table = img2csv::get_cells(&draw_grid(width, height, &lines));
+    assert!(!table.cells().is_empty());
+}
@@ -0,0 +1,161 @@
+extern crate img2csv;
+extern crate image;
+
+mod common;
+
+use common::{read, test_dir};
+use img2csv::{Command, Config, OutputFormat};
+use img2csv::synth::{self, Rng, Style};
+use std::fs::{self, File};
+use std::path::PathBuf;
+
+
+/// Parses a command line given without the program name.
+fn parse(args: &str) -> Result<Config, String> {
+    let args = Some("img2csv").into_iter().chain(args.split_whitespace()).map(str::to_string);
+    Config::new(args)
+}
+
+
+#[test]
+fn test_cli_commands_and_options() {
+    let config = parse("meet.png -o meet.tsv -f tsv --crops crops -v").unwrap();
+    assert_eq!(config.command, Command::Convert);
+    assert_eq!(config.filename, "meet.png");
+    assert_eq!(config.output, Some("meet.tsv".to_string()));
+    assert_eq!(config.format, OutputFormat::Tsv);
+    assert_eq!(config.crop_dir, Some("crops".to_string()));
+    assert!(config.verbose);
+
+    let config = parse("meet.png --binarize otsu --line-min-length 30 --cell-min-width 6").unwrap();
+    assert_eq!(config.params.binarization, img2csv::Binarization::Otsu);
+    assert_eq!(config.params.line_min_length_px, 30);
+    assert_eq!(config.params.cell_min_width_px, 6);
+
+    assert_eq!(parse("cells meet.png").unwrap().command, Command::Cells);
+    assert_eq!(parse("words meet.png --format csv").unwrap().command, Command::Words);
+    assert_eq!(parse("lines meet.png -o lines.png").unwrap().command, Command::Lines);
+
+    // Only the first word can be a command, so an image may be named like one.
+    let config = parse("convert cells").unwrap();
+    assert_eq!((config.command, config.filename.as_str()), (Command::Convert, "cells"));
+
+    // Help needs nothing else, and overrides mistakes in the rest.
+    assert_eq!(parse("--help").unwrap().command, Command::Help);
+    assert_eq!(parse("help").unwrap().command, Command::Help);
+    assert_eq!(parse("meet.png --split-tables -h").unwrap().command, Command::Help);
+    assert_eq!(parse("meet.png --frobnicate --format xml --help").unwrap().command, Command::Help);
+}
+
+
+#[test]
+fn test_cli_errors_name_the_problem() {
+    let error = |args| parse(args).err().unwrap();
+    assert_eq!(error(""), "Missing filename argument.");
+    assert_eq!(error("meet.png --frobnicate"), "Unknown option --frobnicate.");
+    assert_eq!(error("meet.png -o"), "Missing path after -o.");
+    assert_eq!(error("meet.png --format xml"), "Unknown format xml; use csv, tsv or json.");
+    assert_eq!(error("meet.png --binarize median"), "Unknown binarization median; use contrast, otsu, sauvola or fixed:N.");
+    assert_eq!(error("meet.png other.png"), "Too many filename arguments: other.png.");
+    assert_eq!(error("cells meet.png --format json"), "--format json only applies to convert, not cells.");
+    assert_eq!(error("words meet.png --split-tables"), "--split-tables only applies to convert, not words.");
+    assert_eq!(error("lines meet.png"), "lines writes a PNG; give its path with --output.");
+    assert!(error("meet.png --min-confidence 2").contains("from 0 to 1"));
+}
+
+
+/// Saves a synthetic table in its own directory under the temporary
+/// directory, and returns the directory and the table.
+fn save_table(name: &str) -> (PathBuf, synth::Synthetic) {
+    let table = synth::generate(&mut Rng::new(5), &Style::default());
+    let dir = test_dir(&format!("cli-{}", name));
+    table.image.save(&mut File::create(dir.join("results.png")).unwrap(), image::PNG).unwrap();
+    (dir, table)
+}
+
+
+#[test]
+fn test_cli_convert_writes_tsv() {
+    use img2csv::ocr::{MockEngine, Recognition};
+
+    let (dir, table) = save_table("tsv");
+    let output = dir.join("results.tsv");
+    let args = format!("{} -o {} --format tsv", dir.join("results.png").display(), output.display());
+
+    // A tab in the text would split the field, so it becomes a space.
+    let mut texts: Vec<String> = table.cells.iter().map(|c| c.text.clone()).collect();
+    texts[1] = format!("{}\tname", texts[1]);
+    let mut engine = MockEngine::with_responses(texts.into_iter().map(|t| Recognition::new(t, 1.0)).collect());
+    img2csv::run_with_engine(parse(&args).unwrap(), &mut engine).unwrap();
+
+    let mut expected = table.rows.clone();
+    let (row, col) = (table.cells[1].row as usize, table.cells[1].col as usize);
+    expected[row][col] = format!("{} name", expected[row][col]);
+    let expected: String = expected.iter().map(|row| row.join("\t") + "\n").collect();
+    assert_eq!(read(&output), expected);
+}
+
+
+#[test]
+fn test_cli_subcommands_write_their_output() {
+    use image::GenericImage;
+
+    let (dir, table) = save_table("commands");
+    let input = dir.join("results.png");
+    let run = |command: &str, output: &str| {
+        let args = format!("{} {} -o {} --format tsv", command, input.display(), dir.join(output).display());
+        img2csv::run(parse(&args).unwrap()).unwrap();
+        dir.join(output)
+    };
+
+    // One line for each cell, after the header.
+    let cells = read(&run("cells", "cells.tsv"));
+    let mut lines = cells.lines();
+    assert_eq!(lines.next(), Some("table\trow\tcol\trow_span\tcol_span\tx\ty\twidth\theight\tstructure"));
+    let rows: Vec<Vec<&str>> = lines.map(|line| line.split('\t').collect()).collect();
+    assert_eq!(rows.len(), table.cells.len());
+    for (row, truth) in rows.iter().zip(&table.cells) {
+        assert_eq!((row[0], row[1], row[2]), ("1", &*truth.row.to_string(), &*truth.col.to_string()));
+        assert_eq!(row[4], truth.col_span.to_string());
+        let x: i64 = row[5].parse().unwrap();
+        assert!((x - truth.x as i64).abs() <= 2, "{:?}", row);
+    }
+
+    // A box for each word, in whole pixels.
+    let words = read(&run("words", "words.tsv"));
+    let mut lines = words.lines();
+    assert_eq!(lines.next(), Some("x\ty\twidth\theight"));
+    for line in lines {
+        let fields: Vec<i64> = line.split('\t').map(|n| n.parse().unwrap()).collect();
+        assert_eq!(fields.len(), 4);
+        assert!(fields[2] > 0 && fields[3] > 0);
+    }
+
+    // The gridlines, in an image the size of the straight source.
+    let lines = image::open(run("lines", "lines.png")).unwrap();
+    assert_eq!(lines.dimensions(), table.image.dimensions());
+    let first = &table.cells[0];
+    assert!(lines.get_pixel(first.x + first.width / 2, first.y + first.height).data[..3] != [0, 0, 0]);
+    assert!(lines.get_pixel(first.x + first.width / 2, first.y + first.height / 2).data[..3] == [0, 0, 0]);
+}
+
+
+#[test]
+fn test_cli_missing_image_is_named() {
+    use img2csv::ocr::NullEngine;
+
+    let missing = std::env::temp_dir().join("img2csv-test-cli-missing.png");
+    let _ = fs::remove_file(&missing);
+
+    for command in &["convert", "cells", "words", "lines"] {
+        let args = format!("{} {} -o out.png", command, missing.display());
+        let config = parse(&args).unwrap();
+        let error = if config.command == Command::Convert {
+            img2csv::run_with_engine(config, &mut NullEngine)
+        } else {
+            img2csv::run(config)
+        };
+        let error = error.err().unwrap().to_string();
+        assert!(error.starts_with(&format!("Could not read image {}", missing.display())), "{}: {}", command, error);
+    }
+}
@@ -0,0 +1,76 @@
+//! Helpers shared by the integration tests.
+
+#![allow(dead_code)]
+
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use image::{self, DynamicImage, ImageBuffer, Rgba, RgbaImage};
+use img2csv::{self, Config, Table};
+use img2csv::ocr::OcrEngine;
+use img2csv::synth::Synthetic;
+
+
+/// Asserts that the detected table has the rows and columns of the
+/// synthetic table it was found in.
+#[track_caller]
+pub fn assert_same_grid(truth: &Synthetic, detected: &Table) {
+    let rows = truth.cells.last().unwrap().row + 1;
+    let cols = truth.rows[0].len() as u32;
+    assert_eq!((detected.rows(), detected.cols()), (rows, cols), "rows and columns");
+}
+
+
+/// A directory of its own for a test, under the temporary directory.
+pub fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("img2csv-test-{}", name));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+
+/// Saves `img` into `dir`, converts it with the extra command-line `args`
+/// and `engine`, and returns the CSV.
+pub fn convert_to_csv(img: &DynamicImage, dir: &Path, args: &str, engine: &mut OcrEngine) -> String {
+    let (input, output) = (dir.join("input.png"), dir.join("output.csv"));
+    img.save(&mut File::create(&input).unwrap(), image::PNG).unwrap();
+
+    let args = format!("img2csv {} -o {} {}", input.display(), output.display(), args);
+    img2csv::run_with_engine(Config::new(args.split_whitespace().map(str::to_string)).unwrap(), engine).unwrap();
+    read(&output)
+}
+
+
+/// The whole text of a file.
+pub fn read(path: &Path) -> String {
+    let mut text = String::new();
+    File::open(path).unwrap().read_to_string(&mut text).unwrap();
+    text
+}
+
+
+/// Draws black lines on a white image. Each line is (x, y, width, height).
+pub fn draw_grid(width: u32, height: u32, lines: &[(u32, u32, u32, u32)]) -> DynamicImage {
+    let mut img: RgbaImage = ImageBuffer::from_pixel(width, height, Rgba([255, 255, 255, 255]));
+    for &(x, y, w, h) in lines {
+        for py in y .. y + h {
+            for px in x .. x + w {
+                img.put_pixel(px, py, Rgba([0, 0, 0, 255]));
+            }
+        }
+    }
+    DynamicImage::ImageRgba8(img)
+}
+
+
+/// A white page with each image pasted at its top-left corner.
+pub fn page(width: u32, height: u32, images: &[(&DynamicImage, (u32, u32))]) -> DynamicImage {
+    use image::GenericImage;
+
+    let mut page = DynamicImage::ImageRgba8(ImageBuffer::from_pixel(width, height, Rgba([255, 255, 255, 255])));
+    for &(img, (x, y)) in images {
+        page.copy_from(img, x, y);
+    }
+    page
+}
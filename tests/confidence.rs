@@ -0,0 +1,56 @@
+extern crate img2csv;
+extern crate image;
+
+mod common;
+
+use common::draw_grid;
+use image::{DynamicImage, ImageBuffer, Rgba, RgbaImage};
+use img2csv::confidence;
+use img2csv::synth::{self, Rng, Style};
+
+
+#[test]
+fn test_structure_confidence_drops_where_borders_cut_ink() {
+    // Features and lines are white on black. A blob of ink spans x 40 to 60.
+    let black = |width, height| ImageBuffer::from_pixel(width, height, Rgba([0, 0, 0, 255]));
+    let mut features: RgbaImage = black(100, 60);
+    for y in 20 .. 30 {
+        for x in 40 .. 60 {
+            features.put_pixel(x, y, Rgba([255, 255, 255, 255]));
+        }
+    }
+    let features = DynamicImage::ImageRgba8(features);
+    let no_lines = DynamicImage::ImageRgba8(black(100, 60));
+
+    let cell = |x, width| img2csv::Cell {
+        row: 0, col: 0, row_span: 1, col_span: 1,
+        x, y: 0, width, height: 60, structure: 1.0,
+    };
+
+    // A border through the blob cuts it for 10 of its 60 pixels.
+    let cut = confidence::structure(&features, &no_lines, &cell(0, 50));
+    assert!((cut - 50.0 / 60.0).abs() < 1e-3, "{}", cut);
+    assert_eq!(confidence::structure(&features, &no_lines, &cell(0, 80)), 1.0);
+
+    // Along a gridline, the same border is clean.
+    let lines = draw_grid(100, 60, &[(50, 0, 1, 60)]);
+    let mut lines = lines.to_rgba();
+    for px in lines.pixels_mut() {
+        px.data = [255 - px.data[0], 255 - px.data[1], 255 - px.data[2], 255];
+    }
+    let lines = DynamicImage::ImageRgba8(lines);
+    assert_eq!(confidence::structure(&features, &lines, &cell(0, 50)), 1.0);
+}
+
+
+#[test]
+fn test_clean_tables_have_full_structure_confidence() {
+    let mut rng = Rng::new(53);
+    for _ in 0 .. 3 {
+        let table = synth::generate(&mut rng, &Style::default());
+        let detected = img2csv::get_cells(&table.image);
+        for cell in detected.cells() {
+            assert!(cell.structure > 0.95, "{:?}", cell);
+        }
+    }
+}
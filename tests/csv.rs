@@ -0,0 +1,46 @@
+extern crate img2csv;
+
+use img2csv::csv;
+
+
+fn record(fields: &[&str]) -> String {
+    let fields: Vec<String> = fields.iter().map(|f| f.to_string()).collect();
+    let mut out = Vec::new();
+    csv::write_record(&mut out, &fields).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+
+#[test]
+fn test_csv_quotes_only_what_needs_it() {
+    assert_eq!(record(&["1", "Jane Doe", "82.5"]), "1,Jane Doe,82.5\n");
+    assert_eq!(record(&["Doe, Jane", "USAPL"]), "\"Doe, Jane\",USAPL\n");
+    assert_eq!(record(&["5'10\" tall"]), "\"5'10\"\" tall\"\n");
+    assert_eq!(record(&["Open\nMen", "x\ry"]), "\"Open\nMen\",\"x\ry\"\n");
+    assert_eq!(record(&[" padded", "padded "]), "\" padded\",\"padded \"\n");
+}
+
+
+#[test]
+fn test_csv_keeps_empty_cells() {
+    assert_eq!(record(&["", "", ""]), ",,\n");
+    assert_eq!(record(&["1", "", "200"]), "1,,200\n");
+    assert_eq!(record(&[]), "\n");
+
+    let rows = vec![vec!["a".to_string(), String::new()], vec![String::new(), "b".to_string()]];
+    let mut out = Vec::new();
+    csv::write_rows(&mut out, &rows).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "a,\n,b\n");
+}
+
+
+#[test]
+fn test_csv_negates_only_weights() {
+    assert_eq!(csv::failed_attempt("182.5"), "-182.5");
+    assert_eq!(csv::failed_attempt(" 200 "), "-200");
+    assert_eq!(csv::failed_attempt("-182.5"), "-182.5");
+    assert_eq!(csv::failed_attempt("0"), "0");
+    for text in &["inf", "infinity", "NaN", "-inf", "1e40", "X", ""] {
+        assert_eq!(csv::failed_attempt(text), *text);
+    }
+}
@@ -0,0 +1,26 @@
+extern crate img2csv;
+extern crate image;
+
+mod common;
+
+use common::assert_same_grid;
+use img2csv::augment::{self, Degradation};
+use img2csv::deskew;
+use img2csv::synth::{self, Rng, Style};
+
+
+#[test]
+fn test_rotated_table_is_deskewed() {
+    let mut rng = Rng::new(11);
+
+    for &degrees in &[1.5, -0.8] {
+        let mut table = synth::generate(&mut rng, &Style::default());
+        augment::apply_all(&mut table, &[Degradation::Rotate { degrees }], &mut rng).unwrap();
+
+        assert!((deskew::estimate_skew(&table.image) - degrees).abs() < 0.1);
+
+        let detected = img2csv::get_cells(&table.image);
+        assert!((detected.skew() - degrees).abs() < 0.1);
+        assert_same_grid(&table, &detected);
+    }
+}
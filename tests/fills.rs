@@ -0,0 +1,38 @@
+extern crate img2csv;
+extern crate image;
+
+mod common;
+
+use common::assert_same_grid;
+use image::Rgba;
+use img2csv::builtin::BuiltinEngine;
+use img2csv::ocr::OcrEngine;
+use img2csv::synth::{self, Rng, Style};
+
+
+#[test]
+fn test_dark_header_row() {
+    let mut rng = Rng::new(31);
+    let mut engine = BuiltinEngine::new();
+    let styles = [
+        Style { header_fill: Some(Rgba([0, 0, 0, 255])), ..Style::default() },
+        Style {
+            header_fill: Some(Rgba([20, 30, 100, 255])),
+            line_color: Rgba([120, 170, 240, 255]),
+            ..Style::default()
+        },
+    ];
+
+    for style in &styles {
+        let table = synth::generate(&mut rng, style);
+        let detected = img2csv::get_cells(&table.image);
+        assert_same_grid(&table, &detected);
+
+        // The header is split into its cells, and its white text is read.
+        let header = style.title_rows;
+        let texts: Vec<String> = detected.row(header).iter()
+            .map(|cell| engine.recognize(&img2csv::crop_cell(&table.image, cell)).unwrap().text)
+            .collect();
+        assert_eq!(texts, table.rows[header as usize]);
+    }
+}
@@ -0,0 +1,392 @@
+extern crate img2csv;
+extern crate image;
+
+mod common;
+
+use common::{read, test_dir};
+use img2csv::json::Value;
+
+
+fn to_string(value: &Value) -> String {
+    let mut out = Vec::new();
+    value.write(&mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+
+/// Parses a JSON document, such as one written by `Value::write`,
+/// so that the tests can read the output back.
+fn parse(text: &str) -> Result<Value, String> {
+    let mut parser = Parser { chars: text.chars().collect(), pos: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    match parser.peek() {
+        None => Ok(value),
+        Some(ch) => Err(format!("Unexpected {:?} after the JSON value at {}.", ch, parser.pos)),
+    }
+}
+
+
+/// Reads a JSON value character by character.
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).cloned()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| matches!(c, ' ' | '\t' | '\n' | '\r')) {
+            self.pos += 1;
+        }
+    }
+
+    /// Consumes `ch`, after any whitespace, or fails.
+    fn expect(&mut self, ch: char) -> Result<(), String> {
+        self.skip_whitespace();
+        if self.peek() != Some(ch) {
+            return Err(format!("Expected {:?} at {}.", ch, self.pos));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Consumes `word` if it comes next.
+    fn keyword(&mut self, word: &str) -> bool {
+        let end = self.pos + word.chars().count();
+        if end <= self.chars.len() && self.chars[self.pos .. end].iter().cloned().eq(word.chars()) {
+            self.pos = end;
+            return true;
+        }
+        false
+    }
+
+    /// Consumes a run of digits, returning how many there were.
+    fn digits(&mut self) -> usize {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        self.pos - start
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => {
+                self.pos += 1;
+                let mut fields = Vec::new();
+                self.skip_whitespace();
+                if self.peek() == Some('}') {
+                    self.pos += 1;
+                    return Ok(Value::Object(fields));
+                }
+                loop {
+                    self.expect('"')?;
+                    let key = self.string()?;
+                    self.expect(':')?;
+                    fields.push((key, self.value()?));
+                    self.skip_whitespace();
+                    if self.peek() == Some(',') {
+                        self.pos += 1;
+                        continue;
+                    }
+                    self.expect('}')?;
+                    return Ok(Value::Object(fields));
+                }
+            }
+            Some('[') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                self.skip_whitespace();
+                if self.peek() == Some(']') {
+                    self.pos += 1;
+                    return Ok(Value::Array(items));
+                }
+                loop {
+                    items.push(self.value()?);
+                    self.skip_whitespace();
+                    if self.peek() == Some(',') {
+                        self.pos += 1;
+                        continue;
+                    }
+                    self.expect(']')?;
+                    return Ok(Value::Array(items));
+                }
+            }
+            Some('"') => {
+                self.pos += 1;
+                self.string().map(Value::String)
+            }
+            Some(_) if self.keyword("null") => Ok(Value::Null),
+            Some(_) if self.keyword("true") => Ok(Value::Bool(true)),
+            Some(_) if self.keyword("false") => Ok(Value::Bool(false)),
+            Some(_) => self.number(),
+            None => Err("Unexpected end of JSON.".to_string()),
+        }
+    }
+
+    /// Reads a number, which has no leading zeros or plus sign, and
+    /// digits on both sides of its point.
+    fn number(&mut self) -> Result<Value, String> {
+        let start = self.pos;
+        let bad = || format!("Expected a JSON value at {}.", start);
+        if self.peek() == Some('-') {
+            self.pos += 1;
+        }
+        match self.digits() {
+            0 => return Err(bad()),
+            n if n > 1 && self.chars[self.pos - n] == '0' => return Err(bad()),
+            _ => (),
+        }
+        if self.peek() == Some('.') {
+            self.pos += 1;
+            if self.digits() == 0 {
+                return Err(bad());
+            }
+        }
+        if let Some('e') | Some('E') = self.peek() {
+            self.pos += 1;
+            if let Some('+') | Some('-') = self.peek() {
+                self.pos += 1;
+            }
+            if self.digits() == 0 {
+                return Err(bad());
+            }
+        }
+        let number: String = self.chars[start .. self.pos].iter().collect();
+        number.parse().map(Value::Number).map_err(|_| bad())
+    }
+
+    /// Reads the four hex digits of a `\u` escape.
+    fn code_unit(&mut self) -> Result<u32, String> {
+        let hex: String = self.chars.iter().skip(self.pos).take(4).collect();
+        self.pos += 4;
+        match u32::from_str_radix(&hex, 16) {
+            Ok(unit) if hex.len() == 4 => Ok(unit),
+            _ => Err(format!("Bad escape \\u{} in JSON string.", hex)),
+        }
+    }
+
+    /// Reads the rest of a string whose opening quote has been consumed.
+    fn string(&mut self) -> Result<String, String> {
+        let mut s = String::new();
+        loop {
+            let ch = self.peek().ok_or("Unterminated JSON string.")?;
+            self.pos += 1;
+            match ch {
+                '"' => return Ok(s),
+                '\\' => {
+                    let escape = self.peek().ok_or("Unterminated JSON string.")?;
+                    self.pos += 1;
+                    s.push(match escape {
+                        '"' | '\\' | '/' => escape,
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'u' => {
+                            // Characters outside the Basic Multilingual Plane
+                            // are written as a pair of UTF-16 surrogates.
+                            let mut unit = self.code_unit()?;
+                            if (0xd800 .. 0xdc00).contains(&unit) && self.keyword("\\u") {
+                                let low = self.code_unit()?;
+                                if (0xdc00 .. 0xe000).contains(&low) {
+                                    unit = 0x10000 + ((unit - 0xd800) << 10) + (low - 0xdc00);
+                                }
+                            }
+                            std::char::from_u32(unit)
+                                .ok_or_else(|| format!("Unpaired surrogate \\u{:04x} in JSON string.", unit))?
+                        }
+                        other => return Err(format!("Bad escape \\{} in JSON string.", other)),
+                    });
+                }
+                _ if (ch as u32) < 0x20 => return Err(format!("Unescaped {:?} in JSON string.", ch)),
+                _ => s.push(ch),
+            }
+        }
+    }
+}
+
+
+#[test]
+fn test_json_nesting_and_escaping() {
+    let value = Value::object(vec![
+        ("text", "Say \"hi\"\\\n\tnow\u{1}".into()),
+        ("confidence", 0.1f32.into()),
+        ("rows", 3u32.into()),
+        ("failed", false.into()),
+        ("words", Value::Array(vec![])),
+        ("cells", Value::Array(vec![Value::object(vec![("x", 1u32.into())]), Value::Null])),
+    ]);
+
+    assert_eq!(to_string(&value), "\
+{
+  \"text\": \"Say \\\"hi\\\"\\\\\\n\\tnow\\u0001\",
+  \"confidence\": 0.1,
+  \"rows\": 3,
+  \"failed\": false,
+  \"words\": [],
+  \"cells\": [
+    {
+      \"x\": 1
+    },
+    null
+  ]
+}
+");
+}
+
+
+#[test]
+fn test_json_numbers_are_finite() {
+    assert_eq!(to_string(&Value::Number(f64::NAN)), "null\n");
+    assert_eq!(to_string(&Value::Number(-2.5)), "-2.5\n");
+    assert_eq!(to_string(&Value::object(vec![])), "{}\n");
+}
+
+
+#[test]
+fn test_json_parses_what_it_writes() {
+    let value = Value::object(vec![
+        ("text", "Say \"hi\"\\\n\tnow\u{1}".into()),
+        ("confidence", 0.1f32.into()),
+        ("weights", Value::Array(vec![Value::Number(-182.5), Value::Number(1e3)])),
+        ("failed", true.into()),
+        ("words", Value::Array(vec![])),
+        ("original", Value::object(vec![])),
+        ("missing", Value::Null),
+    ]);
+    assert_eq!(parse(&to_string(&value)), Ok(value.clone()));
+    assert_eq!(value.get("failed"), Some(&Value::Bool(true)));
+    assert_eq!(value.get("nothing"), None);
+
+    assert!(parse("{\"rows\": 3,}").is_err());
+    assert!(parse("[1, 2] 3").is_err());
+    assert!(parse("\"open").is_err());
+
+    // Only numbers that JSON allows, and surrogate pairs as one character.
+    for bad in &["01", "+1", "1.", ".5", "1e", "-", "1.5.2", "0x10"] {
+        assert!(parse(bad).is_err(), "{}", bad);
+    }
+    assert_eq!(parse("-0.5e+2"), Ok(Value::Number(-50.0)));
+    assert_eq!(parse("\"\\ud83c\\udfcb\\u00e9\""), Ok(Value::String("\u{1f3cb}\u{e9}".to_string())));
+    assert!(parse("\"\\ud83c\"").is_err());
+}
+
+
+/// The number at `key`, which must be there.
+fn number(value: &Value, key: &str) -> f64 {
+    match value.get(key) {
+        Some(&Value::Number(n)) => n,
+        other => panic!("{} is {:?}, not a number", key, other),
+    }
+}
+
+
+/// The array at `key`, which must be there.
+fn array<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+    match value.get(key) {
+        Some(Value::Array(items)) => items,
+        other => panic!("{} is {:?}, not an array", key, other),
+    }
+}
+
+
+#[test]
+fn test_json_output_describes_every_cell() {
+    use image::GenericImage;
+    use img2csv::ocr::{MockEngine, Recognition};
+    use img2csv::synth::{self, FailedMark, Rng, Style};
+    use img2csv::Config;
+    use std::fs::File;
+
+    let style = Style { failed_mark: FailedMark::RedFill, ..Style::default() };
+    let table = synth::generate(&mut Rng::new(11), &style);
+    assert!(table.rows.iter().flatten().any(|text| text.starts_with('-')));
+
+    let dir = test_dir("json-output");
+    let (input, output, words) = (dir.join("results.png"), dir.join("results.json"), dir.join("words.csv"));
+    table.image.save(&mut File::create(&input).unwrap(), image::PNG).unwrap();
+
+    // The engine reads each weight without its sign, as it would a
+    // failed attempt shown by its fill.
+    let args = format!("img2csv {} -o {} --format json --binarize otsu", input.display(), output.display());
+    let mut engine = MockEngine::with_responses(table.cells.iter()
+        .map(|c| Recognition::new(c.text.trim_start_matches('-'), 0.8))
+        .collect());
+    img2csv::run_with_engine(Config::new(args.split_whitespace().map(str::to_string)).unwrap(), &mut engine).unwrap();
+    let json = parse(&read(&output)).unwrap();
+
+    // Where the image came from and how it was read.
+    let source = json.get("source").unwrap();
+    assert_eq!(source.get("path"), Some(&Value::String(input.display().to_string())));
+    assert_eq!(number(source, "width"), table.image.width() as f64);
+    assert_eq!(number(source, "height"), table.image.height() as f64);
+    assert_eq!(number(&json, "skew"), 0.0);
+    assert_eq!(json.get("inverted"), Some(&Value::Bool(false)));
+    assert_eq!(json.get("binarization").unwrap().get("mode"), Some(&Value::String("otsu".to_string())));
+    let detection = json.get("detection").unwrap();
+    assert_eq!(number(detection, "line_min_length_px"), img2csv::DetectionParams::default().line_min_length_px as f64);
+    assert_eq!(number(&json, "min_confidence"), 0.8);
+
+    let tables = array(&json, "tables");
+    assert_eq!(tables.len(), 1);
+    assert_eq!(number(&tables[0], "rows"), table.rows.len() as f64);
+    assert_eq!(number(&tables[0], "cols"), table.rows[0].len() as f64);
+
+    // The SWT words, as the words command lists them.
+    let args = format!("img2csv words {} -o {}", input.display(), words.display());
+    img2csv::run(Config::new(args.split_whitespace().map(str::to_string)).unwrap()).unwrap();
+    let swt: Vec<Vec<f64>> = read(&words).lines().skip(1)
+        .map(|line| line.split(',').map(|n| n.parse().unwrap()).collect())
+        .collect();
+
+    let cells = array(&tables[0], "cells");
+    assert_eq!(cells.len(), table.cells.len());
+    let mut listed = 0;
+    for (cell, truth) in cells.iter().zip(&table.cells) {
+        let at = format!("cell {},{}", truth.row, truth.col);
+        assert_eq!((number(cell, "row"), number(cell, "col")), (truth.row as f64, truth.col as f64), "{}", at);
+        assert_eq!(number(cell, "col_span"), truth.col_span as f64, "{}", at);
+
+        // Cells cover their interiors, up to the width of a gridline.
+        let close = |key: &str, expected: u32| (number(cell, key) - expected as f64).abs() <= 2.0;
+        assert!(close("x", truth.x) && close("y", truth.y), "{}", at);
+        assert!(close("width", truth.width) && close("height", truth.height), "{}", at);
+
+        // The image was straight, so the original rectangle is the cell's.
+        let original = cell.get("original").unwrap();
+        for key in &["x", "y", "width", "height"] {
+            assert!((number(original, key) - number(cell, key)).abs() <= 1.0, "{} {}", at, key);
+        }
+
+        assert_eq!(cell.get("text"), Some(&Value::String(truth.text.clone())), "{}", at);
+        assert_eq!(cell.get("failed"), Some(&Value::Bool(truth.text.starts_with('-'))), "{}", at);
+        assert_eq!(number(cell, "recognition"), 0.8, "{}", at);
+        let structure = number(cell, "structure");
+        assert!(structure > 0.0 && structure <= 1.0, "{}", at);
+        assert!((number(cell, "confidence") - structure * 0.8).abs() < 1e-3, "{}", at);
+
+        // Exactly the words whose middles are in the cell.
+        let (x, y) = (number(original, "x"), number(original, "y"));
+        let (right, bottom) = (x + number(original, "width"), y + number(original, "height"));
+        let inside: Vec<Vec<f64>> = swt.iter()
+            .filter(|w| {
+                let (mx, my) = ((w[0] + (w[2] / 2.0).floor()), (w[1] + (w[3] / 2.0).floor()));
+                mx >= x && mx < right && my >= y && my < bottom
+            })
+            .cloned()
+            .collect();
+        let found: Vec<Vec<f64>> = array(cell, "words").iter()
+            .map(|w| ["x", "y", "width", "height"].iter().map(|k| number(w, k)).collect())
+            .collect();
+        assert_eq!(found, inside, "{}", at);
+        listed += found.len();
+    }
+    assert!(listed > 0);
+}
@@ -0,0 +1,62 @@
+extern crate img2csv;
+extern crate image;
+
+use img2csv::ocr::{EngineKind, MockEngine, OcrEngine, Recognition};
+use image::DynamicImage;
+use std::path::Path;
+
+
+#[test]
+fn test_mock_engine_replays_responses() {
+    let mut engine = MockEngine::with_responses(vec!(
+        Recognition::new("Name", 0.9),
+        Recognition::new("182.5", 0.5),
+    ));
+    let cell = DynamicImage::new_luma8(40, 12);
+
+    assert_eq!(engine.recognize(&cell).unwrap(), Recognition::new("Name", 0.9));
+    assert_eq!(engine.recognize(&cell).unwrap(), Recognition::new("182.5", 0.5));
+    assert_eq!(engine.recognize(&cell).unwrap(), Recognition::new("40x12", 1.0));
+}
+
+
+#[test]
+fn test_mock_engine_sees_cell_crops() {
+    let filename = "tests/regression/gpc-aus-act-winter-classic.png";
+
+    let img: DynamicImage = image::open(Path::new(&filename)).unwrap();
+    let table = img2csv::get_cells(&img);
+    let mut engine = MockEngine::new();
+
+    for cell in table.cells() {
+        let crop = img2csv::crop_cell(&img, cell);
+        let text = engine.recognize(&crop).unwrap().text;
+        assert_eq!(text, format!("{}x{}", cell.width, cell.height));
+    }
+}
+
+
+#[cfg(not(feature = "tesseract"))]
+#[test]
+fn test_tesseract_needs_the_feature() {
+    assert_eq!(EngineKind::default(), EngineKind::Builtin);
+    assert!(EngineKind::Tesseract.create().is_err());
+}
+
+
+#[cfg(feature = "tesseract")]
+#[test]
+fn test_tesseract_engine() {
+    use img2csv::tesseract::{PageSegMode, TesseractEngine};
+
+    assert_eq!(EngineKind::default(), EngineKind::Tesseract);
+
+    let mut engine = TesseractEngine::new("eng").unwrap();
+    engine.set_page_seg_mode(PageSegMode::SingleWord);
+    engine.set_whitelist(Some("0123456789.-"));
+
+    let mut blank = DynamicImage::new_luma8(40, 12);
+    blank.invert();
+    assert_eq!(engine.recognize(&blank).unwrap().text, "");
+    assert_eq!(engine.recognize_with_whitelist(&blank, Some("0123456789")).unwrap().text, "");
+}
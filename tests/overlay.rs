@@ -0,0 +1,103 @@
+extern crate img2csv;
+extern crate image;
+
+mod common;
+
+use image::GenericImage;
+
+use common::{convert_to_csv, test_dir};
+use img2csv::overlay;
+use img2csv::synth::{self, Rng, Style};
+use img2csv::Bounds;
+
+
+#[test]
+fn test_overlay_outlines_cells_and_words() {
+    let mut rng = Rng::new(61);
+    let table = synth::generate(&mut rng, &Style::default());
+    let tables = img2csv::get_tables(&table.image);
+    let cell = tables[0].cells().iter().find(|c| c.row == 2 && c.col == 1).unwrap().clone();
+
+    let word = Bounds { x: cell.x + 20, y: cell.y + 4, width: 30, height: 10 };
+    let drawn = overlay::draw(&table.image, &tables, &[word]);
+    assert_eq!(drawn.dimensions(), table.image.dimensions());
+
+    // The image is straight, so cells are outlined where they were found.
+    let (right, bottom) = (cell.x + cell.width, cell.y + cell.height);
+    assert_eq!(drawn.get_pixel(cell.x, cell.y), overlay::CELL_COLOR);
+    assert_eq!(drawn.get_pixel(right, bottom), overlay::CELL_COLOR);
+    assert_eq!(drawn.get_pixel(word.x, word.y), overlay::WORD_COLOR);
+    assert_eq!(drawn.get_pixel(word.right(), word.bottom()), overlay::WORD_COLOR);
+
+    // The label sits just inside the top-left corner, and the rest is untouched.
+    let label = (cell.x + 2 .. cell.x + 20).flat_map(|x| (cell.y + 2 .. cell.y + 11).map(move |y| (x, y)))
+        .filter(|&(x, y)| drawn.get_pixel(x, y) == overlay::CELL_COLOR)
+        .count();
+    assert!(label > 10, "{}", label);
+    let (x, y) = (cell.x + cell.width - 3, cell.y + cell.height / 2);
+    assert_eq!(drawn.get_pixel(x, y), table.image.get_pixel(x, y));
+}
+
+
+#[test]
+fn test_overlay_labels_grow_with_the_image() {
+    use img2csv::Table;
+
+    let mut rng = Rng::new(61);
+    let table = synth::generate(&mut rng, &Style::default());
+    let tables = img2csv::get_tables(&table.image);
+
+    // The same table at three times the size.
+    let (width, height) = table.image.dimensions();
+    let big = table.image.resize_exact(3 * width, 3 * height, image::FilterType::Nearest);
+    let times3 = |v: &[u32]| v.iter().map(|n| 3 * n).collect();
+    let big_tables = vec![Table::new(
+        times3(tables[0].row_separators()), times3(tables[0].col_separators()),
+        tables[0].rows(), tables[0].cols(),
+        tables[0].cells().iter()
+            .map(|c| img2csv::Cell { x: 3 * c.x, y: 3 * c.y, width: 3 * c.width, height: 3 * c.height, ..*c })
+            .collect(),
+    )];
+
+    // The label of the cell in row 2, column 1: its pixels inside the cell.
+    let label = |img: &image::DynamicImage, tables: &[Table]| {
+        let cell = tables[0].cells().iter().find(|c| c.row == 2 && c.col == 1).unwrap().clone();
+        let drawn = overlay::draw(img, tables, &[]);
+        (cell.x + 1 .. cell.x + cell.width).flat_map(|x| (cell.y + 1 .. cell.y + cell.height).map(move |y| (x, y)))
+            .filter(|&(x, y)| drawn.get_pixel(x, y) == overlay::CELL_COLOR)
+            .count()
+    };
+
+    // The label is drawn larger, so each of its pixels becomes a square of them.
+    let (small, big) = (label(&table.image, &tables), label(&big, &big_tables));
+    let scale = (1 ..).find(|s| s * s * small >= big).unwrap();
+    assert!(small > 10 && scale > 1 && scale * scale * small == big, "{} {}", small, big);
+}
+
+
+#[test]
+fn test_debug_stages_show_what_each_table_was_found_in() {
+    use image::Rgba;
+    use img2csv::ocr::NullEngine;
+
+    let table = synth::generate(&mut Rng::new(43), &Style { spreadsheet: true, ..Style::default() });
+    let dir = test_dir("debug-stages");
+    convert_to_csv(&table.image, &dir, &format!("--debug-dir {}", dir.display()), &mut NullEngine);
+    let features = image::open(dir.join("features.png")).unwrap();
+    let lines = image::open(dir.join("lines.png")).unwrap();
+    assert_eq!(features.dimensions(), table.image.dimensions());
+    assert!(dir.join("overlay.png").exists() && dir.join("rectified.png").exists());
+
+    // The grid was found in a crop that leaves out the toolbar, so the
+    // edge of its third button is not among the features.
+    let (black, magic) = (Rgba([0, 0, 0, 255]), Rgba([255, 0, 255, 255]));
+    assert!(table.image.get_pixel(64, 25) != table.image.get_pixel(66, 20));
+    assert_eq!(features.get_pixel(64, 25), black);
+    assert_eq!(lines.get_pixel(64, 25), black);
+
+    // The gridline below the first row of data is in both.
+    let first = &table.cells[0];
+    let (x, y) = (first.x + first.width / 2, first.y + first.height);
+    assert_eq!(features.get_pixel(x, y), magic);
+    assert!(lines.get_pixel(x, y) != black);
+}
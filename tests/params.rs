@@ -0,0 +1,124 @@
+extern crate img2csv;
+extern crate image;
+
+use img2csv::{Binarization, DetectionParams};
+use img2csv::synth::{self, Rng, Style};
+
+
+#[test]
+fn test_params_from_text() {
+    let mut params = DetectionParams::default();
+    assert_eq!(params.line_min_length_px, 50);
+    assert_eq!(params.cell_min_width_px, 8);
+
+    params.set_from_str("
+        # Small print from a federation that shrinks its sheets.
+        line_min_length_px = 30
+        line_featureful_threshold = 0.9
+        cell_min_height_px = 6
+
+        binarization = otsu
+    ").unwrap();
+    assert_eq!(params, DetectionParams {
+        binarization: Binarization::Otsu,
+        line_min_length_px: 30,
+        line_featureful_threshold: 0.9,
+        cell_min_height_px: 6,
+        ..DetectionParams::default()
+    });
+
+    assert!(params.set_from_str("cell_min_width_px 6").is_err());
+    assert_eq!(params.set("cell_min_width_px", "0").unwrap_err(),
+               "cell_min_width_px needs a whole number of pixels, at least 2, not 0.");
+    assert_eq!(params.set("line_featureful_threshold", "1.5").unwrap_err(),
+               "line_featureful_threshold needs a fraction above 0 and at most 1, not 1.5.");
+    assert_eq!(params.set("cell_size", "6").unwrap_err(), "Unknown detection parameter cell_size.");
+}
+
+
+#[test]
+fn test_params_change_detection() {
+    let mut rng = Rng::new(67);
+    let table = synth::generate(&mut rng, &Style::default());
+    let cols = table.rows[0].len() as u32;
+
+    let detected = img2csv::get_cells_with(&table.image, &DetectionParams::default());
+    assert_eq!(detected.cols(), cols);
+    assert_eq!(detected.cells().len(), img2csv::get_cells(&table.image).cells().len());
+
+    // Columns narrower than the minimum width are joined to their neighbors.
+    let narrowest = table.cells.iter().filter(|c| c.row > 0).map(|c| c.width).min().unwrap();
+    let params = DetectionParams { cell_min_width_px: narrowest + 10, ..DetectionParams::default() };
+    assert!(img2csv::get_cells_with(&table.image, &params).cols() < cols);
+}
+
+
+#[test]
+fn test_params_edge_values() {
+    let mut params = DetectionParams::default();
+    for name in &["line_min_length_px", "cell_min_height_px", "cell_min_width_px"] {
+        assert!(params.set(name, "1").is_err(), "{}", name);
+        assert!(params.set(name, "-2").is_err(), "{}", name);
+        params.set(name, "2").unwrap();
+    }
+    assert!(params.set("line_featureful_threshold", "0").is_err());
+    params.set("line_featureful_threshold", "1").unwrap();
+    params.set("feature_boundary", "0").unwrap();
+    assert!(params.set("feature_boundary", "-1").is_err());
+    assert_eq!(params, DetectionParams {
+        line_min_length_px: 2,
+        line_featureful_threshold: 1.0,
+        feature_boundary: 0,
+        cell_min_height_px: 2,
+        cell_min_width_px: 2,
+        ..DetectionParams::default()
+    });
+}
+
+
+#[test]
+fn test_params_on_small_images() {
+    use image::{DynamicImage, Rgba};
+
+    let smallest = DetectionParams {
+        line_min_length_px: 2,
+        line_featureful_threshold: 1.0,
+        feature_boundary: 0,
+        cell_min_height_px: 2,
+        cell_min_width_px: 2,
+        ..DetectionParams::default()
+    };
+    let largest = DetectionParams {
+        line_min_length_px: 1000,
+        feature_boundary: 1000,
+        cell_min_height_px: 1000,
+        cell_min_width_px: 1000,
+        ..DetectionParams::default()
+    };
+
+    // A grid of four cells, with lines along every edge and through the middle.
+    for &(width, height) in &[(1, 1), (2, 2), (3, 3), (5, 4), (12, 9), (40, 30)] {
+        let mut img = DynamicImage::new_rgba8(width, height);
+        for (x, y, px) in img.as_mut_rgba8().unwrap().enumerate_pixels_mut() {
+            let is_line = x == 0 || y == 0 || x == width - 1 || y == height - 1 || x == width / 2 || y == height / 2;
+            *px = if is_line { Rgba([0, 0, 0, 255]) } else { Rgba([255, 255, 255, 255]) };
+        }
+
+        // Any valid parameters find cells within the image, if any at all.
+        for params in &[smallest, DetectionParams::default(), largest] {
+            let table = img2csv::get_cells_with(&img, params);
+            assert!(table.cells().iter().all(|c| c.x + c.width <= width && c.y + c.height <= height),
+                    "{}x{} {:?}", width, height, params);
+        }
+    }
+
+    // Where there is room, the smallest cells are found. The border is
+    // left out, since a line along the left edge would cut every row.
+    let mut img = DynamicImage::new_rgba8(40, 30);
+    for (x, y, px) in img.as_mut_rgba8().unwrap().enumerate_pixels_mut() {
+        let is_line = x == 0 || y == 0 || x == 39 || y == 29 || x == 20 || y == 15;
+        *px = if is_line { Rgba([0, 0, 0, 255]) } else { Rgba([255, 255, 255, 255]) };
+    }
+    let table = img2csv::get_cells_with(&img, &DetectionParams { feature_boundary: 2, ..smallest });
+    assert_eq!((table.rows(), table.cols()), (2, 2));
+}
@@ -0,0 +1,69 @@
+extern crate img2csv;
+extern crate image;
+
+mod common;
+
+use common::{assert_same_grid, page};
+use img2csv::augment::{self, Degradation};
+use img2csv::synth::{self, Rng, Style, TruthCell};
+use img2csv::Table;
+
+
+/// Asserts that the middle of each detected cell maps back into the same
+/// cell of the photo.
+fn assert_maps_back(detected: &Table, truth: &[TruthCell]) {
+    for (truth, cell) in truth.iter().zip(detected.cells()) {
+        let (x, y) = detected.to_original(cell.x as f32 + cell.width as f32 / 2.0,
+                                          cell.y as f32 + cell.height as f32 / 2.0);
+        assert!(x >= truth.x as f32 && x <= (truth.x + truth.width) as f32);
+        assert!(y >= truth.y as f32 && y <= (truth.y + truth.height) as f32);
+    }
+}
+
+
+#[test]
+fn test_photographed_table_is_flattened() {
+    let mut rng = Rng::new(13);
+    // A photo has paper around the table, so its outline is not clipped.
+    let style = Style { margin: 12, ..Style::default() };
+
+    for &inset in &[0.02, 0.05] {
+        let mut table = synth::generate(&mut rng, &style);
+        augment::apply_all(&mut table, &[Degradation::Perspective { inset }], &mut rng).unwrap();
+
+        let detected = img2csv::get_cells(&table.image);
+        assert_same_grid(&table, &detected);
+        assert_maps_back(&detected, &table.cells);
+    }
+}
+
+
+#[test]
+fn test_photographed_tables_are_all_flattened() {
+    use image::GenericImage;
+
+    let mut rng = Rng::new(41);
+    let style = Style { margin: 12, ..Style::default() };
+    let first = synth::generate(&mut rng, &style);
+    let second = synth::generate(&mut rng, &style);
+    let (w1, h1) = first.image.dimensions();
+    let (w2, h2) = second.image.dimensions();
+
+    // Two tables on one page, stacked, photographed at an angle.
+    let img = page(40 + u32::max(w1, w2), 64 + h1 + h2, &[(&first.image, (20, 20)), (&second.image, (20, 44 + h1))]);
+
+    let mut cells = Vec::new();
+    for (truth, dy) in [(&first, 20), (&second, 44 + h1)] {
+        cells.push(truth.cells.iter().cloned().map(|mut c| { c.x += 20; c.y += dy; c }).collect::<Vec<_>>());
+    }
+    let split = cells[0].len();
+    let mut photo = synth::Synthetic { image: img, cells: cells.concat(), rows: Vec::new() };
+    augment::apply_all(&mut photo, &[Degradation::Perspective { inset: 0.04 }], &mut rng).unwrap();
+
+    let tables = img2csv::get_tables(&photo.image);
+    assert_eq!(tables.len(), 2);
+    for (detected, (truth, cells)) in tables.iter().zip([(&first, &photo.cells[.. split]), (&second, &photo.cells[split ..])]) {
+        assert_same_grid(truth, detected);
+        assert_maps_back(detected, cells);
+    }
+}
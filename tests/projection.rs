@@ -0,0 +1,25 @@
+extern crate img2csv;
+extern crate image;
+
+mod common;
+
+use common::assert_same_grid;
+use img2csv::synth::{self, Rng, Style};
+
+
+#[test]
+fn test_borderless_table_uses_whitespace() {
+    let mut rng = Rng::new(9);
+    let style = Style {
+        line_width: 0,
+        padding: 8,
+        title_rows: 0,
+        margin: 10,
+        ..Style::default()
+    };
+
+    for _ in 0 .. 3 {
+        let table = synth::generate(&mut rng, &style);
+        assert_same_grid(&table, &img2csv::get_cells(&table.image));
+    }
+}
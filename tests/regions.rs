@@ -0,0 +1,43 @@
+extern crate img2csv;
+extern crate image;
+
+mod common;
+
+use common::{assert_same_grid, page};
+use img2csv::synth::{self, Rng, Style};
+
+
+#[test]
+fn test_multiple_tables_are_separated() {
+    use image::GenericImage;
+
+    let mut rng = Rng::new(41);
+    let first = synth::generate(&mut rng, &Style::default());
+    let second = synth::generate(&mut rng, &Style::default());
+    let (w1, h1) = first.image.dimensions();
+    let (w2, h2) = second.image.dimensions();
+
+    // Stacked, then side by side, with a gap about as wide as a blank row.
+    let layouts = [
+        ((20, 20), (20, 20 + h1 + 24), (40 + u32::max(w1, w2), 60 + h1 + h2)),
+        ((20, 20), (20 + w1 + 40, 20), (80 + w1 + w2, 40 + u32::max(h1, h2))),
+    ];
+
+    for &(a, b, (width, height)) in &layouts {
+        let img = page(width, height, &[(&first.image, a), (&second.image, b)]);
+
+        let tables = img2csv::get_tables(&img);
+        assert_eq!(tables.len(), 2);
+        for (table, (truth, origin)) in tables.iter().zip([(&first, a), (&second, b)]) {
+            assert_same_grid(truth, table);
+
+            let bounds = table.bounds();
+            assert!(bounds.x <= origin.0 && bounds.x + 4 >= origin.0);
+            assert!(bounds.y <= origin.1 && bounds.y + 4 >= origin.1);
+            assert!(table.cells().iter().all(|c| c.x >= bounds.x && c.x + c.width <= bounds.right()));
+        }
+    }
+
+    // A single table is still found whole.
+    assert_eq!(img2csv::get_tables(&first.image).len(), 1);
+}
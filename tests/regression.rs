@@ -7,8 +7,8 @@ use std::path::Path;
 
 
 /// Asserts that each row of cells has the expected number of columns.
-/// The cells vector must be sorted by (row, col).
-fn assert_row_lengths(cells: &Vec<Cell>, lengths: &Vec<u32>) {
+/// The cells must be sorted by (row, col).
+fn assert_row_lengths(cells: &[Cell], lengths: &[u32]) {
     let mut cur_row = 0;
     let mut col_count = 0;
 
@@ -32,8 +32,57 @@ fn test_gpc_aus_act_winter_classic_png() {
     let filename = "tests/regression/gpc-aus-act-winter-classic.png";
 
     let img: DynamicImage = image::open(Path::new(&filename)).unwrap();
-    let cells = img2csv::get_cells(&img);
+    let table = img2csv::get_cells(&img);
 
     let row_lengths = vec!(2,23,23,23,23,23,23,23,23,23,23,23,23,23,23);
-    assert_row_lengths(&cells, &row_lengths);
+    assert_row_lengths(table.cells(), &row_lengths);
+
+    assert_eq!(table.rows(), 15);
+    assert_eq!(table.cols(), 23);
+
+    // The title row is merged, but still covers every column.
+    let title_span: u32 = table.row(0).iter().map(|c| c.col_span).sum();
+    assert_eq!(title_span, 23);
+}
+
+
+#[test]
+fn test_table_columns_are_shared_by_rows() {
+    let filename = "tests/regression/gpc-aus-act-winter-classic.png";
+
+    let img: DynamicImage = image::open(Path::new(&filename)).unwrap();
+    let table = img2csv::get_cells(&img);
+
+    // Every full row lines up with the header row.
+    for row in 2 .. table.rows() {
+        for col in 0 .. table.cols() {
+            let header = table.get(1, col).unwrap();
+            let cell = table.get(row, col).unwrap();
+            assert_eq!((cell.x, cell.width), (header.x, header.width));
+        }
+    }
+}
+
+
+#[test]
+fn test_adaptive_binarization() {
+    use img2csv::Binarization;
+
+    let filename = "tests/regression/gpc-aus-act-winter-classic.png";
+    let img: DynamicImage = image::open(Path::new(&filename)).unwrap();
+    let row_lengths = vec!(2,23,23,23,23,23,23,23,23,23,23,23,23,23,23);
+
+    for mode in &[Binarization::Fixed(130), Binarization::Otsu, Binarization::Sauvola { window: 25, k: 0.2 }] {
+        let table = img2csv::get_cells_with_binarization(&img, mode);
+        assert_row_lengths(table.cells(), &row_lengths);
+        assert!(table.threshold() > 0.0 && table.threshold() < 255.0);
+    }
+}
+
+
+#[test]
+fn test_straight_image_is_not_rotated() {
+    let filename = "tests/regression/gpc-aus-act-winter-classic.png";
+    let img: DynamicImage = image::open(Path::new(&filename)).unwrap();
+    assert_eq!(img2csv::get_cells(&img).skew(), 0.0);
 }
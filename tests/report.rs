@@ -0,0 +1,42 @@
+extern crate img2csv;
+extern crate image;
+
+use img2csv::report::{Entry, Report};
+use img2csv::synth::{self, Rng, Style};
+
+
+#[test]
+fn test_report_rebuilds_table_grid() {
+    let mut rng = Rng::new(59);
+    let table = synth::generate(&mut rng, &Style::default());
+    let detected = img2csv::get_cells(&table.image);
+
+    let entries: Vec<Entry> = detected.cells().iter().map(|cell| {
+        Entry {
+            cell: cell.clone(),
+            bounds: detected.original_bounds(cell),
+            crop: img2csv::crop_cell_color(&table.image, cell),
+            text: if cell.row == 0 { "<Title & \"Co\">".to_string() } else { format!("{}-{}", cell.row, cell.col) },
+            confidence: if cell.col == 0 { 0.2 } else { 1.0 },
+        }
+    }).collect();
+
+    let mut report = Report::new("meet.png", &table.image);
+    report.add_table(&detected, entries);
+    let mut html = Vec::new();
+    report.write(&mut html).unwrap();
+    let html = String::from_utf8(html).unwrap();
+
+    assert_eq!(html.matches("<tr>").count(), detected.rows() as usize);
+    assert_eq!(html.matches("<td ").count(), detected.cells().len());
+    assert_eq!(html.matches("data:image/png;base64,iVBORw0KGgo").count(), detected.cells().len() + 1);
+
+    // The merged title spans the table, and its text is escaped.
+    assert!(html.contains(&format!("colspan=\"{}\"", detected.cols())));
+    assert!(html.contains("&lt;Title &amp; &quot;Co&quot;&gt;"));
+    assert!(!html.contains("<Title"));
+
+    // Low confidence is red, high confidence green.
+    assert!(html.contains("hsl(24, 70%, 85%)"));
+    assert!(html.contains("hsl(120, 70%, 85%)"));
+}
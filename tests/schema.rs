@@ -0,0 +1,64 @@
+extern crate img2csv;
+
+use img2csv::schema::Schema;
+
+
+fn row(cells: &[&str]) -> Vec<String> {
+    cells.iter().map(|c| c.to_string()).collect()
+}
+
+
+#[test]
+fn test_headers_map_to_openpowerlifting_columns() {
+    let schema = Schema::openpowerlifting();
+    let pairs = [
+        ("Place", "Place"), ("Name", "Name"), ("Sex", "Sex"), ("Div", "Division"),
+        ("WtCls (Kg)", "WeightClassKg"), ("BWt (Kg)", "BodyweightKg"),
+        ("Squat 1", "Squat1Kg"), ("SQUAT-3", "Squat3Kg"), ("Best Squat", "Best3SquatKg"),
+        ("Bench 2", "Bench2Kg"), ("Best Deadlift", "Best3DeadliftKg"),
+        ("PL Total", "TotalKg"), ("Total", "TotalKg"), ("TotalKg", "TotalKg"),
+    ];
+    for &(header, canonical) in pairs.iter() {
+        assert_eq!(schema.canonical(header), Some(canonical), "{}", header);
+    }
+
+    assert_eq!(schema.canonical("Coeff Score"), None);
+    assert_eq!(schema.canonical(""), None);
+}
+
+
+#[test]
+fn test_header_row_is_found_below_titles() {
+    let schema = Schema::openpowerlifting();
+    let rows = vec![
+        row(&["2018 State Championships", "", "", ""]),
+        row(&["Place", "Name", "BWt", "Lot"]),
+        row(&["1", "Jane Doe", "62.1", "7"]),
+    ];
+
+    // Title rows stay, so every record of the table is still written.
+    let mapped = schema.map_rows(rows);
+    assert_eq!(mapped.len(), 3);
+    assert_eq!(mapped[0], row(&["2018 State Championships", "", "", ""]));
+    assert_eq!(mapped[1], row(&["Place", "Name", "BodyweightKg", "Lot"]));
+    assert_eq!(mapped[2], row(&["1", "Jane Doe", "62.1", "7"]));
+
+    // Without a recognizable header, nothing changes.
+    let rows = vec![row(&["1", "Jane Doe"]), row(&["2", "John Doe"])];
+    assert_eq!(schema.map_rows(rows.clone()), rows);
+}
+
+
+#[test]
+fn test_synonyms_file_extends_schema() {
+    let mut schema = Schema::openpowerlifting();
+    schema.extend_from_str("# Club-specific headers\n\nWeightClassKg = Klasse, Gew. Kl.\nLot = Lot #\n").unwrap();
+
+    assert_eq!(schema.canonical("Klasse"), Some("WeightClassKg"));
+    assert_eq!(schema.canonical("gew kl"), Some("WeightClassKg"));
+    assert_eq!(schema.canonical("Lot #"), Some("Lot"));
+    assert_eq!(schema.canonical("BWt"), Some("BodyweightKg"));
+
+    assert!(schema.extend_from_str("no equals sign").is_err());
+    assert!(schema.extend_from_str("= Nameless").is_err());
+}
@@ -0,0 +1,43 @@
+extern crate img2csv;
+extern crate image;
+
+mod common;
+
+use common::assert_same_grid;
+use image::{DynamicImage, GenericImage};
+use img2csv::style;
+use img2csv::synth::{self, FailedMark, Rng, Style};
+
+
+#[test]
+fn test_failed_attempts_are_flagged() {
+    let mut rng = Rng::new(47);
+    let marks = [FailedMark::RedText, FailedMark::RedFill, FailedMark::Strikethrough, FailedMark::Cross];
+
+    for &mark in marks.iter() {
+        let mut table = synth::generate(&mut rng, &Style { failed_mark: mark, ..Style::default() });
+        assert!(table.rows.iter().flatten().any(|text| text.starts_with('-')));
+
+        // The marks are not mistaken for gridlines.
+        let detected = img2csv::get_cells(&table.image);
+        assert_same_grid(&table, &detected);
+        assert_eq!(detected.cells().len(), table.cells.len(), "{:?}", mark);
+
+        for cell in &table.cells {
+            let crop = table.image.sub_image(cell.x, cell.y, cell.width, cell.height).to_image();
+            let found = style::analyze(&DynamicImage::ImageRgba8(crop));
+            assert_eq!(found.is_failed(), cell.text.starts_with('-'), "{:?} on {:?}", mark, cell.text);
+            if found.is_failed() {
+                assert_eq!(found.red_text, mark == FailedMark::RedText);
+                assert_eq!(found.red_fill, mark == FailedMark::RedFill);
+            }
+        }
+    }
+
+    // Minus signs alone are not styling.
+    let mut table = synth::generate(&mut rng, &Style::default());
+    for cell in &table.cells {
+        let crop = table.image.sub_image(cell.x, cell.y, cell.width, cell.height).to_image();
+        assert!(!style::analyze(&DynamicImage::ImageRgba8(crop)).is_failed(), "{:?}", cell.text);
+    }
+}
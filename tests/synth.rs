@@ -0,0 +1,93 @@
+extern crate img2csv;
+extern crate image;
+
+mod common;
+
+use common::assert_same_grid;
+use img2csv::synth::{self, Rng, Style};
+
+
+#[test]
+fn test_synthetic_rows_match_ground_truth() {
+    let mut rng = Rng::new(1);
+    let style = Style::default();
+
+    for _ in 0 .. 5 {
+        let table = synth::generate(&mut rng, &style);
+        let detected = img2csv::get_cells(&table.image);
+        assert_same_grid(&table, &detected);
+
+        for row in 0 .. detected.rows() {
+            let expected = table.cells.iter().filter(|c| c.row == row).count();
+            assert_eq!(detected.row(row).len(), expected, "in row {}", row);
+
+            for (truth, cell) in table.cells.iter().filter(|c| c.row == row).zip(detected.row(row)) {
+                assert_eq!((cell.col, cell.col_span), (truth.col, truth.col_span));
+            }
+        }
+    }
+}
+
+
+#[test]
+fn test_synthetic_is_reproducible() {
+    let a = synth::generate(&mut Rng::new(7), &Style::random(&mut Rng::new(7)));
+    let b = synth::generate(&mut Rng::new(7), &Style::random(&mut Rng::new(7)));
+    assert_eq!(a.rows, b.rows);
+    assert_eq!(a.cells, b.cells);
+    assert_eq!(a.image.raw_pixels(), b.image.raw_pixels());
+}
+
+
+#[test]
+fn test_marks_stay_within_narrow_columns() {
+    use img2csv::synth::FailedMark;
+
+    let mut rng = Rng::new(71);
+    let blue = image::Rgba([0, 0, 255, 255]);
+
+    // Columns narrower than their text: one with less padding than a
+    // stroke, and one with no room for text between the padding at all.
+    for &(mark, padding, font_scale) in [(FailedMark::Strikethrough, 1, 3), (FailedMark::Cross, 3, 2)].iter() {
+        let style = Style {
+            failed_mark: mark, col_widths: vec![6; 20], padding, font_scale, title_rows: 0, text_color: blue,
+            ..Style::default()
+        };
+        let table = synth::generate(&mut rng, &style);
+        let img = table.image.to_rgba();
+
+        // Text starts at least a pixel in, so only a mark from the left could
+        // reach the first column of a cell that is not marked itself.
+        for cell in table.cells.iter().filter(|c| !c.text.starts_with('-')) {
+            assert_eq!(cell.width, 6);
+            for y in cell.y .. cell.y + cell.height {
+                assert!(*img.get_pixel(cell.x, y) != blue, "{:?} at {},{}", mark, cell.row, cell.col);
+            }
+        }
+    }
+}
+
+
+#[test]
+fn test_columns_narrower_than_their_padding() {
+    let mut rng = Rng::new(72);
+
+    // With no margin or gridlines, the first column starts at the edge,
+    // and right-aligned text would start left of the image.
+    let style = Style { col_widths: vec![3; 20], padding: 5, line_width: 0, margin: 0, ..Style::default() };
+    let table = synth::generate(&mut rng, &style);
+    assert!(table.cells.iter().all(|c| c.width >= 3));
+}
+
+
+#[test]
+fn test_sans_tables_match_ground_truth() {
+    use img2csv::synth::Font;
+
+    let mut rng = Rng::new(73);
+    for &size in &[12.0, 16.0] {
+        let style = Style { font: Font::Sans(size), bold: size > 14.0, ..Style::default() };
+        let table = synth::generate(&mut rng, &style);
+        assert_same_grid(&table, &img2csv::get_cells(&table.image));
+    }
+}
@@ -0,0 +1,73 @@
+extern crate img2csv;
+extern crate image;
+
+mod common;
+
+use common::{convert_to_csv, draw_grid, test_dir};
+use image::Rgba;
+
+
+#[test]
+fn test_merged_cells_have_spans() {
+    // A 3x3 grid whose top row is one merged cell, and whose
+    // leftmost column merges the bottom two rows.
+    let img = draw_grid(300, 200, &[
+        (0, 0, 300, 1),
+        (0, 66, 300, 1),
+        (100, 133, 200, 1),
+        (0, 199, 300, 1),
+        (0, 0, 1, 200),
+        (100, 66, 1, 134),
+        (200, 66, 1, 134),
+        (299, 0, 1, 200),
+    ]);
+
+    let table = img2csv::get_cells(&img);
+    assert_eq!((table.rows(), table.cols()), (3, 3));
+
+    let title = table.get(0, 0).unwrap();
+    assert_eq!((title.row_span, title.col_span), (1, 3));
+    assert!(table.get(0, 1).is_none());
+
+    let merged = table.get(1, 0).unwrap();
+    assert_eq!((merged.row_span, merged.col_span), (2, 1));
+    assert!(table.get(2, 0).is_none());
+    assert_eq!(table.covering(2, 0).unwrap().row, 1);
+
+    let plain = table.get(2, 2).unwrap();
+    assert_eq!((plain.row_span, plain.col_span), (1, 1));
+}
+
+
+#[test]
+fn test_failed_attempts_need_an_attempt_header() {
+    use img2csv::ocr::{MockEngine, Recognition};
+
+    // A 3x3 grid whose Place and third column are filled pink in the
+    // middle row, as federations mark failed attempts.
+    let mut img = draw_grid(300, 150, &[
+        (0, 0, 300, 1), (0, 50, 300, 1), (0, 100, 300, 1), (0, 149, 300, 1),
+        (0, 0, 1, 150), (100, 0, 1, 150), (200, 0, 1, 150), (299, 0, 1, 150),
+    ]);
+    for &x in &[1, 201] {
+        for y in 51 .. 100 {
+            for dx in 0 .. 99 {
+                img.as_mut_rgba8().unwrap().put_pixel(x + dx, y, Rgba([253, 153, 203, 255]));
+            }
+        }
+    }
+
+    let dir = test_dir("failed-attempts");
+    let convert = |texts: &[&str]| {
+        let mut engine = MockEngine::with_responses(texts.iter().map(|t| Recognition::new(*t, 1.0)).collect());
+        convert_to_csv(&img, &dir, "", &mut engine)
+    };
+
+    // Only the column named as an attempt is negated.
+    assert_eq!(convert(&["Place", "Name", "Squat 1", "1", "Jane", "140", "2", "John", "150"]),
+               "Place,Name,Squat 1\n1,Jane,-140\n2,John,150\n");
+
+    // Without a header, no column is known to hold attempts.
+    assert_eq!(convert(&["1", "Jane", "140", "2", "John", "150", "3", "Jim", "160"]),
+               "1,Jane,140\n2,John,150\n3,Jim,160\n");
+}